@@ -0,0 +1,12 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::check_requirements;
+
+pub async fn check_requirements_cmd(name: String, remote: Option<&Client>) -> Result<()> {
+    let report = match remote {
+        Some(client) => client.check_requirements(&name).await?,
+        None => check_requirements(&name).await?,
+    };
+    print!("{}", report);
+    Ok(())
+}
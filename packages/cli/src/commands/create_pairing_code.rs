@@ -0,0 +1,16 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::create_pairing_code;
+use kittynode_core::domain::auth::Scope;
+
+pub async fn create_pairing_code_cmd(scope: Scope, remote: Option<&Client>) -> Result<()> {
+    let pairing_code = match remote {
+        Some(client) => client.create_pairing_code(scope).await?,
+        None => create_pairing_code(scope)?,
+    };
+    println!(
+        "Pairing code {} grants the '{}' scope until {}",
+        pairing_code.code, pairing_code.scope, pairing_code.expires_at
+    );
+    Ok(())
+}
@@ -1,6 +1,14 @@
 use eyre::Result;
+use kittynode_client::Client;
 use kittynode_core::application::delete_package;
 
-pub async fn delete_package_cmd(name: String, include_images: bool) -> Result<()> {
-    delete_package(&name, include_images).await
+pub async fn delete_package_cmd(
+    name: String,
+    include_images: bool,
+    remote: Option<&Client>,
+) -> Result<()> {
+    match remote {
+        Some(client) => client.delete_package(&name, include_images).await,
+        None => delete_package(&name, include_images).await,
+    }
 }
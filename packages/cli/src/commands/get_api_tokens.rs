@@ -0,0 +1,16 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::get_api_tokens;
+
+pub async fn get_api_tokens_cmd(remote: Option<&Client>) -> Result<()> {
+    for token in match remote {
+        Some(client) => client.get_api_tokens().await?,
+        None => get_api_tokens()?,
+    } {
+        println!(
+            "{} ({}), created {}",
+            token.name, token.scope, token.created_at
+        );
+    }
+    Ok(())
+}
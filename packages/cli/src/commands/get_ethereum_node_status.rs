@@ -0,0 +1,12 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::get_ethereum_node_status;
+
+pub async fn get_ethereum_node_status_cmd(remote: Option<&Client>) -> Result<()> {
+    let status = match remote {
+        Some(client) => client.get_ethereum_node_status().await?,
+        None => get_ethereum_node_status().await?,
+    };
+    print!("{}", status);
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::get_package_metrics;
+
+pub async fn get_package_metrics_cmd(name: String, remote: Option<&Client>) -> Result<()> {
+    let metrics = match remote {
+        Some(client) => client.get_package_metrics(&name).await?,
+        None => get_package_metrics(&name).await?,
+    };
+    print!("{}", metrics);
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::get_package_status;
+
+pub async fn get_package_status_cmd(name: String, remote: Option<&Client>) -> Result<()> {
+    let status = match remote {
+        Some(client) => client.get_package_status(&name).await?,
+        None => get_package_status(&name).await?,
+    };
+    print!("{}", status);
+    Ok(())
+}
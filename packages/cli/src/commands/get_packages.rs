@@ -1,8 +1,12 @@
 use eyre::Result;
+use kittynode_client::Client;
 use kittynode_core::application::get_packages;
 
-pub async fn get_packages_cmd() -> Result<()> {
-    let packages = get_packages()?;
+pub async fn get_packages_cmd(remote: Option<&Client>) -> Result<()> {
+    let packages = match remote {
+        Some(client) => client.get_packages().await?,
+        None => get_packages()?,
+    };
     for package in packages.values() {
         println!("{}", package);
     }
@@ -0,0 +1,12 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::get_tls_certificate;
+
+pub async fn get_tls_certificate_cmd(remote: Option<&Client>) -> Result<()> {
+    let certificate = match remote {
+        Some(client) => client.get_tls_certificate().await?,
+        None => get_tls_certificate()?,
+    };
+    println!("{}", certificate);
+    Ok(())
+}
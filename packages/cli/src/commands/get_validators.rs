@@ -0,0 +1,14 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::get_validators;
+
+pub async fn get_validators_cmd(remote: Option<&Client>) -> Result<()> {
+    let keys = match remote {
+        Some(client) => client.get_validators().await?,
+        None => get_validators().await?,
+    };
+    for key in keys {
+        println!("{}", key);
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use eyre::{Context, Result};
+use kittynode_client::Client;
+use kittynode_core::application::import_validator_keystores;
+use kittynode_core::domain::validator::KeystoreImport;
+use std::fs;
+use std::path::PathBuf;
+
+pub async fn import_validator_keystores_cmd(
+    keystores: Vec<PathBuf>,
+    password_file: PathBuf,
+    slashing_protection: Option<PathBuf>,
+    remote: Option<&Client>,
+) -> Result<()> {
+    let read = |path: &PathBuf| {
+        fs::read_to_string(path).wrap_err_with(|| format!("Failed to read {}", path.display()))
+    };
+    let password = read(&password_file)?
+        .trim_end_matches(['\r', '\n'])
+        .to_string();
+    let keystores = keystores
+        .iter()
+        .map(|path| {
+            Ok(KeystoreImport {
+                keystore: read(path)?,
+                password: password.clone(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let slashing_protection = slashing_protection.as_ref().map(read).transpose()?;
+
+    let results = match remote {
+        Some(client) => {
+            client
+                .import_validator_keystores(keystores, slashing_protection)
+                .await?
+        }
+        None => import_validator_keystores(keystores, slashing_protection).await?,
+    };
+    for result in results {
+        println!("{}", result);
+    }
+    Ok(())
+}
@@ -1,6 +1,58 @@
-use eyre::Result;
-use kittynode_core::application::install_package;
+use eyre::{Result, bail};
+use kittynode_client::Client;
+use kittynode_core::application::{check_requirements, install_package_with_progress};
+use kittynode_core::domain::image::PullProgress;
+use kittynode_core::domain::requirements::CheckStatus;
+use std::io::Write;
 
-pub async fn install_package_cmd(name: String) -> Result<()> {
-    install_package(&name).await
+const BAR_WIDTH: usize = 30;
+
+pub async fn install_package_cmd(name: String, force: bool, remote: Option<&Client>) -> Result<()> {
+    let report = match remote {
+        Some(client) => client.check_requirements(&name).await?,
+        None => check_requirements(&name).await?,
+    };
+    if report.status() != CheckStatus::Pass {
+        eprint!("{}", report);
+    }
+    if report.status() == CheckStatus::Fail && !force {
+        bail!(
+            "This host does not meet the requirements of {}, use --force to install anyway",
+            name
+        );
+    }
+
+    match remote {
+        Some(client) => {
+            client
+                .install_package_with_progress(&name, print_progress)
+                .await
+        }
+        None => install_package_with_progress(&name, print_progress).await,
+    }
+}
+
+/// Redraws a progress bar for the image being pulled on stderr.
+fn print_progress(progress: &PullProgress) {
+    let filled = match progress.total_bytes {
+        0 => 0,
+        total => {
+            let ratio = progress.downloaded_bytes.min(total) as f64 / total as f64;
+            (ratio * BAR_WIDTH as f64) as usize
+        }
+    };
+    let filled = if progress.done { BAR_WIDTH } else { filled };
+
+    let mut stderr = std::io::stderr().lock();
+    let _ = write!(
+        stderr,
+        "\r\x1b[2K[{}{}] {}",
+        "#".repeat(filled),
+        " ".repeat(BAR_WIDTH - filled),
+        progress
+    );
+    if progress.done {
+        let _ = writeln!(stderr);
+    }
+    let _ = stderr.flush();
 }
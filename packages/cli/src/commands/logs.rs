@@ -0,0 +1,34 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::stream_container_logs;
+use kittynode_core::domain::logs::{LogSource, LogsQuery};
+use tokio_stream::StreamExt;
+
+pub async fn logs_cmd(
+    container_name: String,
+    query: LogsQuery,
+    timestamps: bool,
+    remote: Option<&Client>,
+) -> Result<()> {
+    let mut stream = match remote {
+        Some(client) => {
+            client
+                .stream_container_logs(&container_name, &query)
+                .await?
+        }
+        None => stream_container_logs(&container_name, &query)?,
+    };
+    while let Some(line) = stream.next().await {
+        let line = line?;
+        let text = if timestamps {
+            line.to_string()
+        } else {
+            line.message
+        };
+        match line.source {
+            LogSource::Stdout => println!("{}", text),
+            LogSource::Stderr => eprintln!("{}", text),
+        }
+    }
+    Ok(())
+}
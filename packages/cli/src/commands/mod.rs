@@ -1,7 +1,49 @@
+mod check_requirements;
+mod create_pairing_code;
 mod delete_package;
+mod get_api_tokens;
+mod get_ethereum_node_status;
+mod get_package_metrics;
+mod get_package_status;
 mod get_packages;
+mod get_tls_certificate;
+mod get_validators;
+mod import_validator_keystores;
 mod install_package;
+mod logs;
+mod pair_server;
+mod remove_validators;
+mod restart_package;
+mod revoke_api_token;
+mod set_tls_certificate;
+mod set_validator_fee_recipient;
+mod set_validator_graffiti;
+mod start_package;
+mod stop_package;
+mod unpair_server;
+mod upgrade_package;
 
+pub use check_requirements::check_requirements_cmd;
+pub use create_pairing_code::create_pairing_code_cmd;
 pub use delete_package::delete_package_cmd;
+pub use get_api_tokens::get_api_tokens_cmd;
+pub use get_ethereum_node_status::get_ethereum_node_status_cmd;
+pub use get_package_metrics::get_package_metrics_cmd;
+pub use get_package_status::get_package_status_cmd;
 pub use get_packages::get_packages_cmd;
+pub use get_tls_certificate::get_tls_certificate_cmd;
+pub use get_validators::get_validators_cmd;
+pub use import_validator_keystores::import_validator_keystores_cmd;
 pub use install_package::install_package_cmd;
+pub use logs::logs_cmd;
+pub use pair_server::pair_server_cmd;
+pub use remove_validators::remove_validators_cmd;
+pub use restart_package::restart_package_cmd;
+pub use revoke_api_token::revoke_api_token_cmd;
+pub use set_tls_certificate::set_tls_certificate_cmd;
+pub use set_validator_fee_recipient::set_validator_fee_recipient_cmd;
+pub use set_validator_graffiti::set_validator_graffiti_cmd;
+pub use start_package::start_package_cmd;
+pub use stop_package::stop_package_cmd;
+pub use unpair_server::unpair_server_cmd;
+pub use upgrade_package::upgrade_package_cmd;
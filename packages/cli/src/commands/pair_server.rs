@@ -0,0 +1,18 @@
+use eyre::Result;
+use kittynode_client::Client;
+
+pub async fn pair_server_cmd(server_url: String, code: String, name: String) -> Result<()> {
+    let pairing = Client::pair(&server_url, &code, &name).await?;
+    pairing.save()?;
+    println!(
+        "Paired with {} as '{}' with the '{}' scope",
+        pairing.client.server_url(),
+        pairing.token.name,
+        pairing.token.scope
+    );
+    println!(
+        "Check that the server logs the certificate fingerprint {}",
+        pairing.fingerprint
+    );
+    Ok(())
+}
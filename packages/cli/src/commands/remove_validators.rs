@@ -0,0 +1,18 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::remove_validators;
+
+pub async fn remove_validators_cmd(pubkeys: Vec<String>, remote: Option<&Client>) -> Result<()> {
+    let removed = match remote {
+        Some(client) => client.remove_validators(pubkeys).await?,
+        None => remove_validators(pubkeys).await?,
+    };
+    for result in &removed.results {
+        println!("{}", result);
+    }
+    println!(
+        "Slashing protection interchange saved to {}",
+        removed.slashing_protection_path
+    );
+    Ok(())
+}
@@ -0,0 +1,10 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::restart_package;
+
+pub async fn restart_package_cmd(name: String, remote: Option<&Client>) -> Result<()> {
+    match remote {
+        Some(client) => client.restart_package(&name).await,
+        None => restart_package(&name).await,
+    }
+}
@@ -0,0 +1,12 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::revoke_api_token;
+
+pub async fn revoke_api_token_cmd(name: String, remote: Option<&Client>) -> Result<()> {
+    match remote {
+        Some(client) => client.revoke_api_token(&name).await?,
+        None => revoke_api_token(&name)?,
+    }
+    println!("Revoked API token '{}'", name);
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use eyre::{Result, bail};
+use kittynode_client::Client;
+use kittynode_core::application::set_tls_certificate;
+use kittynode_core::domain::tls::TlsCertificatePaths;
+use std::path::PathBuf;
+
+pub async fn set_tls_certificate_cmd(
+    cert: Option<PathBuf>,
+    key: Option<PathBuf>,
+    self_signed: bool,
+    remote: Option<&Client>,
+) -> Result<()> {
+    let paths = match (cert, key, self_signed) {
+        (Some(cert), Some(key), false) => Some((cert, key)),
+        (None, None, true) => None,
+        _ => bail!("Pass a certificate and key, or --self-signed"),
+    };
+    let certificate = match remote {
+        // The paths are on the server host, so they are sent as given
+        Some(client) => {
+            let paths = paths.map(|(cert, key)| TlsCertificatePaths {
+                cert_path: cert.display().to_string(),
+                key_path: key.display().to_string(),
+            });
+            client.set_tls_certificate(paths).await?
+        }
+        None => set_tls_certificate(
+            paths
+                .as_ref()
+                .map(|(cert, key)| (cert.as_path(), key.as_path())),
+        )?,
+    };
+    println!("{}", certificate);
+    println!("Restart the web server and pair clients again to use it");
+    Ok(())
+}
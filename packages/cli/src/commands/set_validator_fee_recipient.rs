@@ -0,0 +1,14 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::set_validator_fee_recipient;
+
+pub async fn set_validator_fee_recipient_cmd(
+    pubkey: String,
+    address: String,
+    remote: Option<&Client>,
+) -> Result<()> {
+    match remote {
+        Some(client) => client.set_validator_fee_recipient(&pubkey, &address).await,
+        None => set_validator_fee_recipient(&pubkey, &address).await,
+    }
+}
@@ -0,0 +1,14 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::set_validator_graffiti;
+
+pub async fn set_validator_graffiti_cmd(
+    pubkey: String,
+    graffiti: String,
+    remote: Option<&Client>,
+) -> Result<()> {
+    match remote {
+        Some(client) => client.set_validator_graffiti(&pubkey, &graffiti).await,
+        None => set_validator_graffiti(&pubkey, &graffiti).await,
+    }
+}
@@ -0,0 +1,10 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::start_package;
+
+pub async fn start_package_cmd(name: String, remote: Option<&Client>) -> Result<()> {
+    match remote {
+        Some(client) => client.start_package(&name).await,
+        None => start_package(&name).await,
+    }
+}
@@ -0,0 +1,10 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::stop_package;
+
+pub async fn stop_package_cmd(name: String, remote: Option<&Client>) -> Result<()> {
+    match remote {
+        Some(client) => client.stop_package(&name).await,
+        None => stop_package(&name).await,
+    }
+}
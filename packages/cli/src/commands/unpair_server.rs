@@ -0,0 +1,8 @@
+use eyre::Result;
+use kittynode_client::Pairing;
+
+pub async fn unpair_server_cmd() -> Result<()> {
+    Pairing::forget()?;
+    println!("Forgot the paired server");
+    Ok(())
+}
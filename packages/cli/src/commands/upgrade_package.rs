@@ -0,0 +1,10 @@
+use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::application::upgrade_package;
+
+pub async fn upgrade_package_cmd(name: String, remote: Option<&Client>) -> Result<()> {
+    match remote {
+        Some(client) => client.upgrade_package(&name).await,
+        None => upgrade_package(&name).await,
+    }
+}
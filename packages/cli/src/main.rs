@@ -1,20 +1,65 @@
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
 use eyre::Result;
+use kittynode_client::Client;
+use kittynode_core::domain::auth::Scope;
+use kittynode_core::domain::logs::LogsQuery;
+use std::path::PathBuf;
 mod commands;
 
 #[derive(Parser)]
 #[command(about, version)]
 struct Cli {
+    #[arg(
+        long,
+        global = true,
+        help = "Run the command on the server paired with pair-server"
+    )]
+    remote: bool,
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
+    CheckRequirements {
+        #[arg(value_name = "PACKAGE_NAME")]
+        name: String,
+    },
+    GetEthereumNodeStatus,
     GetPackages,
+    GetPackageMetrics {
+        #[arg(value_name = "PACKAGE_NAME")]
+        name: String,
+    },
+    GetPackageStatus {
+        #[arg(value_name = "PACKAGE_NAME")]
+        name: String,
+    },
     InstallPackage {
         #[arg(value_name = "PACKAGE_NAME")]
         name: String,
+        #[arg(
+            long,
+            help = "Install even if the host does not meet the package requirements"
+        )]
+        force: bool,
+    },
+    StartPackage {
+        #[arg(value_name = "PACKAGE_NAME")]
+        name: String,
+    },
+    StopPackage {
+        #[arg(value_name = "PACKAGE_NAME")]
+        name: String,
+    },
+    RestartPackage {
+        #[arg(value_name = "PACKAGE_NAME")]
+        name: String,
+    },
+    UpgradePackage {
+        #[arg(value_name = "PACKAGE_NAME")]
+        name: String,
     },
     DeletePackage {
         #[arg(value_name = "PACKAGE_NAME")]
@@ -22,23 +67,217 @@ enum Commands {
         #[arg(long = "include-images", help = "Whether to include Docker images")]
         include_images: bool,
     },
+    GetValidators,
+    ImportValidatorKeystores {
+        #[arg(value_name = "KEYSTORE_FILE", required = true)]
+        keystores: Vec<PathBuf>,
+        #[arg(long, help = "File containing the password of the keystores")]
+        password_file: PathBuf,
+        #[arg(
+            long,
+            help = "EIP-3076 slashing protection interchange of keys used in another validator client"
+        )]
+        slashing_protection: Option<PathBuf>,
+    },
+    RemoveValidators {
+        #[arg(value_name = "PUBKEY", required = true)]
+        pubkeys: Vec<String>,
+    },
+    SetValidatorFeeRecipient {
+        #[arg(value_name = "PUBKEY")]
+        pubkey: String,
+        #[arg(value_name = "ADDRESS")]
+        address: String,
+    },
+    SetValidatorGraffiti {
+        #[arg(value_name = "PUBKEY")]
+        pubkey: String,
+        #[arg(value_name = "GRAFFITI")]
+        graffiti: String,
+    },
+    CreatePairingCode {
+        #[arg(
+            long,
+            default_value = "read",
+            help = "The scope of the API token the code is exchanged for, read or admin"
+        )]
+        scope: Scope,
+    },
+    GetApiTokens,
+    RevokeApiToken {
+        #[arg(value_name = "CLIENT_NAME")]
+        name: String,
+    },
+    PairServer {
+        #[arg(value_name = "SERVER_URL")]
+        server_url: String,
+        #[arg(value_name = "PAIRING_CODE")]
+        code: String,
+        #[arg(
+            long,
+            default_value = "Kittynode CLI",
+            help = "The name of the API token on the server"
+        )]
+        name: String,
+    },
+    UnpairServer,
+    GetTlsCertificate,
+    SetTlsCertificate {
+        #[arg(value_name = "CERT_PEM", requires = "key")]
+        cert: Option<PathBuf>,
+        #[arg(value_name = "KEY_PEM")]
+        key: Option<PathBuf>,
+        #[arg(
+            long,
+            conflicts_with = "cert",
+            help = "Serve the self-signed certificate again"
+        )]
+        self_signed: bool,
+    },
+    Logs {
+        #[arg(value_name = "CONTAINER_NAME")]
+        container_name: String,
+        #[arg(short, long, help = "Keep streaming new log lines")]
+        follow: bool,
+        #[arg(
+            short = 'n',
+            long,
+            help = "Number of lines to show from the end of the logs"
+        )]
+        tail: Option<usize>,
+        #[arg(long, help = "Only show lines written at or after this RFC 3339 time")]
+        since: Option<DateTime<Utc>>,
+        #[arg(long, help = "Only show lines written before this RFC 3339 time")]
+        until: Option<DateTime<Utc>>,
+        #[arg(long, conflicts_with = "stderr_only", help = "Only show stdout")]
+        stdout_only: bool,
+        #[arg(long, help = "Only show stderr")]
+        stderr_only: bool,
+        #[arg(short, long, help = "Prefix each line with its timestamp")]
+        timestamps: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
-    match Cli::parse().command {
+    let cli = Cli::parse();
+    let remote = if cli.remote {
+        Some(Client::from_config()?)
+    } else {
+        None
+    };
+    let remote = remote.as_ref();
+    match cli.command {
+        Commands::CheckRequirements { name } => {
+            commands::check_requirements_cmd(name, remote).await?;
+        }
+        Commands::GetEthereumNodeStatus => {
+            commands::get_ethereum_node_status_cmd(remote).await?;
+        }
         Commands::GetPackages => {
-            commands::get_packages_cmd().await?;
+            commands::get_packages_cmd(remote).await?;
+        }
+        Commands::GetPackageMetrics { name } => {
+            commands::get_package_metrics_cmd(name, remote).await?;
+        }
+        Commands::GetPackageStatus { name } => {
+            commands::get_package_status_cmd(name, remote).await?;
+        }
+        Commands::InstallPackage { name, force } => {
+            commands::install_package_cmd(name, force, remote).await?;
         }
-        Commands::InstallPackage { name } => {
-            commands::install_package_cmd(name).await?;
+        Commands::StartPackage { name } => {
+            commands::start_package_cmd(name, remote).await?;
+        }
+        Commands::StopPackage { name } => {
+            commands::stop_package_cmd(name, remote).await?;
+        }
+        Commands::RestartPackage { name } => {
+            commands::restart_package_cmd(name, remote).await?;
+        }
+        Commands::UpgradePackage { name } => {
+            commands::upgrade_package_cmd(name, remote).await?;
         }
         Commands::DeletePackage {
             name,
             include_images,
         } => {
-            commands::delete_package_cmd(name, include_images).await?;
+            commands::delete_package_cmd(name, include_images, remote).await?;
+        }
+        Commands::GetValidators => {
+            commands::get_validators_cmd(remote).await?;
+        }
+        Commands::ImportValidatorKeystores {
+            keystores,
+            password_file,
+            slashing_protection,
+        } => {
+            commands::import_validator_keystores_cmd(
+                keystores,
+                password_file,
+                slashing_protection,
+                remote,
+            )
+            .await?;
+        }
+        Commands::RemoveValidators { pubkeys } => {
+            commands::remove_validators_cmd(pubkeys, remote).await?;
+        }
+        Commands::SetValidatorFeeRecipient { pubkey, address } => {
+            commands::set_validator_fee_recipient_cmd(pubkey, address, remote).await?;
+        }
+        Commands::SetValidatorGraffiti { pubkey, graffiti } => {
+            commands::set_validator_graffiti_cmd(pubkey, graffiti, remote).await?;
+        }
+        Commands::CreatePairingCode { scope } => {
+            commands::create_pairing_code_cmd(scope, remote).await?;
+        }
+        Commands::GetApiTokens => {
+            commands::get_api_tokens_cmd(remote).await?;
+        }
+        Commands::RevokeApiToken { name } => {
+            commands::revoke_api_token_cmd(name, remote).await?;
+        }
+        Commands::PairServer {
+            server_url,
+            code,
+            name,
+        } => {
+            commands::pair_server_cmd(server_url, code, name).await?;
+        }
+        Commands::UnpairServer => {
+            commands::unpair_server_cmd().await?;
+        }
+        Commands::GetTlsCertificate => {
+            commands::get_tls_certificate_cmd(remote).await?;
+        }
+        Commands::SetTlsCertificate {
+            cert,
+            key,
+            self_signed,
+        } => {
+            commands::set_tls_certificate_cmd(cert, key, self_signed, remote).await?;
+        }
+        Commands::Logs {
+            container_name,
+            follow,
+            tail,
+            since,
+            until,
+            stdout_only,
+            stderr_only,
+            timestamps,
+        } => {
+            let query = LogsQuery {
+                tail,
+                follow,
+                since,
+                until,
+                stdout: !stderr_only,
+                stderr: !stdout_only,
+            };
+            commands::logs_cmd(container_name, query, timestamps, remote).await?;
         }
     }
     Ok(())
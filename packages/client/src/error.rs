@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// The error returned when the server answers with an unsuccessful status. Errors the server
+/// describes as JSON are returned as their core type instead, such as
+/// [`ConfigValidationError`](kittynode_core::domain::config_schema::ConfigValidationError).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The server answered {}", self.status)?;
+        if !self.message.is_empty() {
+            write!(f, ": {}", self.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StatusError {}
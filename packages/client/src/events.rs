@@ -0,0 +1,72 @@
+/// A server-sent event, such as a `progress` event with its JSON data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ServerEvent {
+    pub(crate) kind: String,
+    pub(crate) data: String,
+}
+
+/// Splits a server-sent event stream into events as its chunks arrive.
+#[derive(Debug, Default)]
+pub(crate) struct EventReader {
+    /// Raw bytes, as a chunk can end inside a multi-byte character.
+    buffer: Vec<u8>,
+}
+
+impl EventReader {
+    /// Adds a chunk of the stream, returning the events it completes.
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Vec<ServerEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.windows(2).position(|bytes| bytes == b"\n\n") {
+            let event: Vec<u8> = self.buffer.drain(..end + 2).collect();
+            let event = String::from_utf8_lossy(&event);
+            let mut kind = String::new();
+            let mut data = Vec::new();
+            for field in event.lines() {
+                if let Some(value) = field.strip_prefix("event:") {
+                    kind = value.trim().to_string();
+                } else if let Some(value) = field.strip_prefix("data:") {
+                    data.push(value.strip_prefix(' ').unwrap_or(value));
+                }
+            }
+            // Keep-alive comments have neither
+            if !kind.is_empty() || !data.is_empty() {
+                events.push(ServerEvent {
+                    kind,
+                    data: data.join("\n"),
+                });
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_events_split_across_chunks() {
+        let mut reader = EventReader::default();
+        assert!(reader.push(b"event: progress\ndata: {\"done\":").is_empty());
+        let events = reader.push(b"true}\n\n:\n\nevent: log\ndata: first\ndata: second\n\n");
+        assert_eq!(
+            events,
+            vec![
+                ServerEvent {
+                    kind: "progress".into(),
+                    data: "{\"done\":true}".into(),
+                },
+                ServerEvent {
+                    kind: "log".into(),
+                    data: "first\nsecond".into(),
+                },
+            ]
+        );
+
+        let message = "event: log\ndata: café\n\n".as_bytes();
+        let split = message.len() - 3;
+        assert!(reader.push(&message[..split]).is_empty());
+        assert_eq!(reader.push(&message[split..])[0].data, "café");
+    }
+}
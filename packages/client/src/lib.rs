@@ -0,0 +1,457 @@
+//! A typed client for the Kittynode web API, which `kittynode-web` serves and describes in its
+//! `openapi.json`. Methods are named after the `kittynode_core::application` use cases they call.
+
+mod error;
+mod events;
+mod tls;
+
+pub use error::StatusError;
+
+use events::EventReader;
+use eyre::{Result, bail, eyre};
+use kittynode_core::domain::auth::{ApiToken, ApiTokenInfo, PairRequest, PairingCode, Scope};
+use kittynode_core::domain::config_schema::ConfigValidationError;
+use kittynode_core::domain::image::{ImageLock, PullProgress};
+use kittynode_core::domain::logs::{LogLine, LogLineStream, LogsQuery};
+use kittynode_core::domain::metrics::PackageMetrics;
+use kittynode_core::domain::node_status::EthereumNodeStatus;
+use kittynode_core::domain::package::{DeleteOptions, Package, PackageConfig};
+use kittynode_core::domain::package_status::PackageStatus;
+use kittynode_core::domain::ports::{PortConflict, PortConflictError};
+use kittynode_core::domain::requirements::RequirementsReport;
+use kittynode_core::domain::system_info::SystemInfo;
+use kittynode_core::domain::tls::{TlsCertificate, TlsCertificatePaths};
+use kittynode_core::domain::validator::{
+    KeystoreImport, KeystoreImportRequest, KeystoreImportResult, RemovedValidators, ValidatorKey,
+};
+use reqwest::{Method, RequestBuilder, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tls::PinnedCertVerifier;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// A client of a Kittynode web server, trusting only its pinned certificate.
+#[derive(Debug, Clone)]
+pub struct Client {
+    server_url: String,
+    token: String,
+    http: reqwest::Client,
+}
+
+/// A server paired with, and the API token and certificate fingerprint to keep for it.
+#[derive(Debug, Clone)]
+pub struct Pairing {
+    pub client: Client,
+    pub token: ApiToken,
+    pub fingerprint: String,
+}
+
+impl Pairing {
+    /// Records the server in the Kittynode config, for [`Client::from_config`].
+    pub fn save(&self) -> Result<()> {
+        kittynode_core::application::set_server_url(self.client.server_url.clone())?;
+        kittynode_core::application::set_server_token(self.token.token.clone())?;
+        kittynode_core::application::set_server_fingerprint(self.fingerprint.clone())
+    }
+
+    /// Forgets the server recorded by [`Pairing::save`], with its API token and pinned
+    /// certificate fingerprint.
+    pub fn forget() -> Result<()> {
+        kittynode_core::application::set_server_url(String::new())?;
+        kittynode_core::application::set_server_token(String::new())?;
+        kittynode_core::application::set_server_fingerprint(String::new())
+    }
+}
+
+impl Client {
+    /// Returns a client of a server paired with before, such as
+    /// `https://kittynode.local:3000`.
+    pub fn new(server_url: &str, token: String, fingerprint: String) -> Result<Self> {
+        Ok(Self {
+            server_url: server_url.trim_end_matches('/').to_string(),
+            token,
+            http: Arc::new(PinnedCertVerifier::pinned(fingerprint)).http_client()?,
+        })
+    }
+
+    /// Returns a client of the server recorded in the Kittynode config on pairing.
+    pub fn from_config() -> Result<Self> {
+        let server_url = kittynode_core::application::get_server_url()?;
+        if server_url.is_empty() {
+            bail!("Not paired with a server");
+        }
+        Self::new(
+            &server_url,
+            kittynode_core::application::get_server_token()?,
+            kittynode_core::application::get_server_fingerprint()?,
+        )
+    }
+
+    /// Exchanges a pairing code for an API token named after this client. The certificate the
+    /// server presents is trusted and pinned, so compare its fingerprint with the one the
+    /// server logs.
+    pub async fn pair(server_url: &str, code: &str, name: &str) -> Result<Pairing> {
+        if !server_url.starts_with("https://") {
+            bail!("The server URL must start with https://");
+        }
+        let verifier = Arc::new(PinnedCertVerifier::trust_on_first_use());
+        let mut client = Self {
+            server_url: server_url.trim_end_matches('/').to_string(),
+            token: String::new(),
+            http: verifier.clone().http_client()?,
+        };
+        let request = PairRequest {
+            code: code.to_string(),
+            name: name.to_string(),
+        };
+        let token: ApiToken = send(client.request(Method::POST, &["pair"])?.json(&request))
+            .await?
+            .json()
+            .await?;
+        let fingerprint = verifier
+            .seen()
+            .ok_or_else(|| eyre!("The server did not present a certificate"))?;
+
+        client.http = Arc::new(PinnedCertVerifier::pinned(fingerprint.clone())).http_client()?;
+        client.token = token.token.clone();
+        Ok(Pairing {
+            client,
+            token,
+            fingerprint,
+        })
+    }
+
+    #[must_use]
+    pub fn server_url(&self) -> &str {
+        &self.server_url
+    }
+
+    pub async fn create_pairing_code(&self, scope: Scope) -> Result<PairingCode> {
+        self.post_json(&["pairing_codes"], &scope).await
+    }
+
+    pub async fn get_api_tokens(&self) -> Result<Vec<ApiTokenInfo>> {
+        self.get(&["api_tokens"]).await
+    }
+
+    pub async fn revoke_api_token(&self, name: &str) -> Result<()> {
+        self.call(Method::DELETE, &["api_tokens", name]).await
+    }
+
+    pub async fn get_capabilities(&self) -> Result<Vec<String>> {
+        self.get(&["capabilities"]).await
+    }
+
+    pub async fn add_capability(&self, name: &str) -> Result<()> {
+        self.call(Method::PUT, &["capabilities", name]).await
+    }
+
+    pub async fn remove_capability(&self, name: &str) -> Result<()> {
+        self.call(Method::DELETE, &["capabilities", name]).await
+    }
+
+    pub async fn get_packages(&self) -> Result<HashMap<String, Package>> {
+        self.get(&["packages"]).await
+    }
+
+    pub async fn get_installed_packages(&self) -> Result<Vec<Package>> {
+        self.get(&["packages", "installed"]).await
+    }
+
+    /// Installs a package. Taken host ports are returned as a [`PortConflictError`].
+    pub async fn install_package(&self, name: &str) -> Result<()> {
+        let request = self.request(Method::POST, &["packages", name, "install"])?;
+        send_or::<PortConflictError>(request, StatusCode::CONFLICT).await?;
+        Ok(())
+    }
+
+    /// Installs a package, reporting the progress of each image pull.
+    pub async fn install_package_with_progress(
+        &self,
+        name: &str,
+        on_progress: impl Fn(&PullProgress),
+    ) -> Result<()> {
+        let request = self.request(Method::POST, &["packages", name, "install", "stream"])?;
+        let mut response = send(request).await?;
+        let mut reader = EventReader::default();
+        while let Some(chunk) = response.chunk().await? {
+            for event in reader.push(&chunk) {
+                match event.kind.as_str() {
+                    "progress" => on_progress(&serde_json::from_str(&event.data)?),
+                    "done" => return Ok(()),
+                    "error" => bail!(event.data),
+                    _ => {}
+                }
+            }
+        }
+        bail!("The server closed the stream before the install finished")
+    }
+
+    pub async fn upgrade_package(&self, name: &str) -> Result<()> {
+        self.call(Method::POST, &["packages", name, "upgrade"])
+            .await
+    }
+
+    pub async fn start_package(&self, name: &str) -> Result<()> {
+        self.call(Method::POST, &["packages", name, "start"]).await
+    }
+
+    pub async fn stop_package(&self, name: &str) -> Result<()> {
+        self.call(Method::POST, &["packages", name, "stop"]).await
+    }
+
+    pub async fn restart_package(&self, name: &str) -> Result<()> {
+        self.call(Method::POST, &["packages", name, "restart"])
+            .await
+    }
+
+    pub async fn delete_package(&self, name: &str, include_images: bool) -> Result<()> {
+        let request = self
+            .request(Method::DELETE, &["packages", name])?
+            .query(&DeleteOptions { include_images });
+        send(request).await?;
+        Ok(())
+    }
+
+    pub async fn get_package_config(&self, name: &str) -> Result<PackageConfig> {
+        self.get(&["packages", name, "config"]).await
+    }
+
+    /// Updates the config of a package. A config that does not match the package schema is
+    /// returned as a [`ConfigValidationError`].
+    pub async fn update_package_config(&self, name: &str, config: PackageConfig) -> Result<()> {
+        let request = self
+            .request(Method::PUT, &["packages", name, "config"])?
+            .json(&config);
+        send_or::<ConfigValidationError>(request, StatusCode::UNPROCESSABLE_ENTITY).await?;
+        Ok(())
+    }
+
+    pub async fn get_package_status(&self, name: &str) -> Result<PackageStatus> {
+        self.get(&["packages", name, "status"]).await
+    }
+
+    pub async fn get_package_metrics(&self, name: &str) -> Result<PackageMetrics> {
+        self.get(&["packages", name, "metrics"]).await
+    }
+
+    pub async fn get_installed_images(&self, name: &str) -> Result<ImageLock> {
+        self.get(&["packages", name, "images"]).await
+    }
+
+    pub async fn check_requirements(&self, name: &str) -> Result<RequirementsReport> {
+        self.get(&["packages", name, "requirements"]).await
+    }
+
+    pub async fn check_port_conflicts(&self, name: &str) -> Result<Vec<PortConflict>> {
+        self.get(&["packages", name, "port_conflicts"]).await
+    }
+
+    pub async fn get_ethereum_node_status(&self) -> Result<EthereumNodeStatus> {
+        self.get(&["ethereum", "status"]).await
+    }
+
+    pub async fn get_validators(&self) -> Result<Vec<ValidatorKey>> {
+        self.get(&["validators"]).await
+    }
+
+    pub async fn import_validator_keystores(
+        &self,
+        keystores: Vec<KeystoreImport>,
+        slashing_protection: Option<String>,
+    ) -> Result<Vec<KeystoreImportResult>> {
+        let request = KeystoreImportRequest {
+            keystores,
+            slashing_protection,
+        };
+        self.post_json(&["validators"], &request).await
+    }
+
+    pub async fn remove_validators(&self, pubkeys: Vec<String>) -> Result<RemovedValidators> {
+        let request = self
+            .request(Method::DELETE, &["validators"])?
+            .json(&pubkeys);
+        Ok(send(request).await?.json().await?)
+    }
+
+    pub async fn set_validator_fee_recipient(&self, pubkey: &str, address: &str) -> Result<()> {
+        self.put(&["validators", pubkey, "fee_recipient"], &address)
+            .await
+    }
+
+    pub async fn set_validator_graffiti(&self, pubkey: &str, graffiti: &str) -> Result<()> {
+        self.put(&["validators", pubkey, "graffiti"], &graffiti)
+            .await
+    }
+
+    pub async fn get_container_logs(
+        &self,
+        container_name: &str,
+        query: &LogsQuery,
+    ) -> Result<Vec<LogLine>> {
+        let request = self
+            .request(Method::GET, &["containers", container_name, "logs"])?
+            .query(query);
+        Ok(send(request).await?.json().await?)
+    }
+
+    /// Streams the logs of a container, ending with an error if the server reports one.
+    pub async fn stream_container_logs(
+        &self,
+        container_name: &str,
+        query: &LogsQuery,
+    ) -> Result<LogLineStream> {
+        let request = self
+            .request(
+                Method::GET,
+                &["containers", container_name, "logs", "stream"],
+            )?
+            .query(query);
+        let mut response = send(request).await?;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut reader = EventReader::default();
+            loop {
+                let chunk = match response.chunk().await {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => return,
+                    Err(e) => {
+                        let _ = sender.send(Err(e.into()));
+                        return;
+                    }
+                };
+                for event in reader.push(&chunk) {
+                    let line = match event.kind.as_str() {
+                        "log" => serde_json::from_str(&event.data).map_err(Into::into),
+                        "error" => Err(eyre!(event.data)),
+                        _ => continue,
+                    };
+                    let failed = line.is_err();
+                    if sender.send(line).is_err() || failed {
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(Box::pin(UnboundedReceiverStream::new(receiver)))
+    }
+
+    pub async fn is_docker_running(&self) -> Result<bool> {
+        let response = self
+            .request(Method::GET, &["docker", "running"])?
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::SERVICE_UNAVAILABLE => Ok(false),
+            status if status.is_success() => Ok(true),
+            _ => Err(status_error(response).await),
+        }
+    }
+
+    pub async fn get_system_info(&self) -> Result<SystemInfo> {
+        self.get(&["system_info"]).await
+    }
+
+    pub async fn init_kittynode(&self) -> Result<()> {
+        self.call(Method::POST, &["kittynode"]).await
+    }
+
+    pub async fn delete_kittynode(&self) -> Result<()> {
+        self.call(Method::DELETE, &["kittynode"]).await
+    }
+
+    pub async fn get_tls_certificate(&self) -> Result<TlsCertificate> {
+        self.get(&["tls_certificate"]).await
+    }
+
+    /// Makes the server serve a certificate and key at paths on its host, or the self-signed
+    /// certificate again when `None`, once it restarts.
+    pub async fn set_tls_certificate(
+        &self,
+        paths: Option<TlsCertificatePaths>,
+    ) -> Result<TlsCertificate> {
+        let request = match paths {
+            Some(paths) => self
+                .request(Method::PUT, &["tls_certificate"])?
+                .json(&paths),
+            None => self.request(Method::DELETE, &["tls_certificate"])?,
+        };
+        Ok(send(request).await?.json().await?)
+    }
+
+    /// Returns a request to the route with the given path segments, which are escaped.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder> {
+        let mut url = Url::parse(&self.server_url)?;
+        url.path_segments_mut()
+            .map_err(|()| eyre!("Invalid server URL '{}'", self.server_url))?
+            .pop_if_empty()
+            .extend(segments);
+        let request = self.http.request(method, url);
+        Ok(match self.token.is_empty() {
+            true => request,
+            false => request.bearer_auth(&self.token),
+        })
+    }
+
+    async fn get<T: DeserializeOwned>(&self, segments: &[&str]) -> Result<T> {
+        Ok(send(self.request(Method::GET, segments)?)
+            .await?
+            .json()
+            .await?)
+    }
+
+    async fn post_json<T: DeserializeOwned>(
+        &self,
+        segments: &[&str],
+        body: &impl serde::Serialize,
+    ) -> Result<T> {
+        let request = self.request(Method::POST, segments)?.json(body);
+        Ok(send(request).await?.json().await?)
+    }
+
+    async fn put(&self, segments: &[&str], body: &impl serde::Serialize) -> Result<()> {
+        send(self.request(Method::PUT, segments)?.json(body)).await?;
+        Ok(())
+    }
+
+    async fn call(&self, method: Method, segments: &[&str]) -> Result<()> {
+        send(self.request(method, segments)?).await?;
+        Ok(())
+    }
+}
+
+/// Sends a request, returning a [`StatusError`] for an unsuccessful status.
+async fn send(request: RequestBuilder) -> Result<Response> {
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(status_error(response).await);
+    }
+    Ok(response)
+}
+
+/// Sends a request like [`send`], returning the error the server describes as JSON with the
+/// given status as its core type.
+async fn send_or<E>(request: RequestBuilder, status: StatusCode) -> Result<Response>
+where
+    E: DeserializeOwned + std::error::Error + Send + Sync + 'static,
+{
+    let response = request.send().await?;
+    if response.status() == status {
+        return Err(response.json::<E>().await?.into());
+    }
+    if !response.status().is_success() {
+        return Err(status_error(response).await);
+    }
+    Ok(response)
+}
+
+async fn status_error(response: Response) -> eyre::Report {
+    StatusError {
+        status: response.status().as_u16(),
+        message: response.text().await.unwrap_or_default(),
+    }
+    .into()
+}
@@ -0,0 +1,114 @@
+use eyre::Result;
+use kittynode_core::domain::tls::fingerprint;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{CryptoProvider, ring, verify_tls12_signature, verify_tls13_signature};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use std::sync::{Arc, Mutex};
+
+/// Trusts the server by the fingerprint of its certificate rather than a certificate
+/// authority, as the web server serves a self-signed certificate by default.
+#[derive(Debug)]
+pub(crate) struct PinnedCertVerifier {
+    /// The fingerprint to accept, or `None` to accept any certificate while pairing.
+    pinned: Option<String>,
+    /// The fingerprint of the last certificate the server presented.
+    seen: Mutex<Option<String>>,
+    provider: Arc<CryptoProvider>,
+}
+
+impl PinnedCertVerifier {
+    /// Accepts only the certificate with the given fingerprint.
+    pub(crate) fn pinned(fingerprint: String) -> Self {
+        Self::new(Some(fingerprint))
+    }
+
+    /// Accepts any certificate and records its fingerprint, for pairing.
+    pub(crate) fn trust_on_first_use() -> Self {
+        Self::new(None)
+    }
+
+    fn new(pinned: Option<String>) -> Self {
+        Self {
+            pinned,
+            seen: Mutex::new(None),
+            provider: Arc::new(ring::default_provider()),
+        }
+    }
+
+    /// Returns the fingerprint of the last certificate the server presented.
+    pub(crate) fn seen(&self) -> Option<String> {
+        self.seen.lock().ok()?.clone()
+    }
+
+    /// Returns an HTTP client that verifies servers with this verifier.
+    pub(crate) fn http_client(self: Arc<Self>) -> Result<reqwest::Client> {
+        let tls = rustls::ClientConfig::builder_with_provider(self.provider.clone())
+            .with_safe_default_protocol_versions()?
+            .dangerous()
+            .with_custom_certificate_verifier(self)
+            .with_no_client_auth();
+        Ok(reqwest::Client::builder()
+            .use_preconfigured_tls(tls)
+            .build()?)
+    }
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let fingerprint = fingerprint(end_entity);
+        if let Some(pinned) = &self.pinned
+            && *pinned != fingerprint
+        {
+            return Err(rustls::Error::General(format!(
+                "The server certificate fingerprint {} does not match the one pinned on pairing, {}",
+                fingerprint, pinned
+            )));
+        }
+        if let Ok(mut seen) = self.seen.lock() {
+            *seen = Some(fingerprint);
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
@@ -0,0 +1,8 @@
+use crate::domain::auth::Scope;
+use crate::infra::auth::AuthStore;
+use eyre::Result;
+
+/// Returns the scope of an API token of the web server, or `None` if it is not known.
+pub fn authorize_token(token: &str) -> Result<Option<Scope>> {
+    Ok(AuthStore::load()?.authorize(token))
+}
@@ -0,0 +1,13 @@
+use crate::domain::ports::PortConflict;
+use crate::infra::{
+    docker::DockerRuntime, package::get_package, package_config::PackageConfigStore, ports,
+};
+use eyre::Result;
+
+/// Finds the host ports a package would publish with its current config that are already taken.
+pub async fn check_port_conflicts(name: &str) -> Result<Vec<PortConflict>> {
+    let config = PackageConfigStore::load(name)?;
+    let package = get_package(name, &config)?;
+    let runtime = DockerRuntime::connect()?;
+    ports::check_port_conflicts(&runtime, &package).await
+}
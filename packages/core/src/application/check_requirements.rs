@@ -0,0 +1,31 @@
+use crate::application::get_system_info;
+use crate::domain::requirements::RequirementsReport;
+use crate::infra::{
+    docker::DockerRuntime, package::get_package, package_config::PackageConfigStore,
+    runtime::ContainerRuntime,
+};
+use eyre::Result;
+use tracing::warn;
+
+/// Checks whether the host has the cores, memory and free disk a package needs with its
+/// current config.
+pub async fn check_requirements(name: &str) -> Result<RequirementsReport> {
+    let config = PackageConfigStore::load(name)?;
+    let package = get_package(name, &config)?;
+    let system = get_system_info()?;
+
+    let data_root = match DockerRuntime::connect() {
+        Ok(runtime) => runtime.data_root().await,
+        Err(e) => Err(e),
+    };
+    let data_root = data_root
+        .inspect_err(|e| warn!("Failed to get the Docker data root: {}", e))
+        .ok();
+
+    Ok(RequirementsReport::check(
+        &package.name,
+        &package.requirements,
+        &system,
+        data_root.as_deref(),
+    ))
+}
@@ -0,0 +1,24 @@
+use crate::domain::auth::{PairingCode, Scope};
+use crate::infra::auth::{AuthStore, generate_pairing_code};
+use chrono::{TimeDelta, Utc};
+use eyre::Result;
+
+/// How long a pairing code can be redeemed for.
+const PAIRING_CODE_LIFETIME: TimeDelta = TimeDelta::minutes(10);
+
+/// Creates a one-time code a client can exchange for an API token with the given scope.
+pub fn create_pairing_code(scope: Scope) -> Result<PairingCode> {
+    let now = Utc::now();
+    let pairing_code = PairingCode {
+        code: generate_pairing_code(),
+        scope,
+        expires_at: now + PAIRING_CODE_LIFETIME,
+    };
+    AuthStore::update(|state| {
+        state
+            .pairing_codes
+            .retain(|pending| pending.expires_at > now);
+        state.pairing_codes.push(pairing_code.clone());
+    })?;
+    Ok(pairing_code)
+}
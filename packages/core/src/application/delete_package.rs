@@ -1,14 +1,85 @@
-use crate::infra::package::{self, get_packages};
+use crate::application::get_installed_packages::get_installed_packages_with_runtime;
+use crate::infra::{
+    docker::DockerRuntime,
+    file::kittynode_path,
+    image_lock::ImageLockStore,
+    metrics_history::MetricsHistoryStore,
+    monitoring,
+    package::{self, get_package},
+    package_config::PackageConfigStore,
+    runtime::ContainerRuntime,
+};
 use eyre::Result;
-use tracing::info;
+use tracing::{info, warn};
 
 pub async fn delete_package(name: &str, include_images: bool) -> Result<()> {
-    let package = get_packages()?
-        .get(name)
-        .ok_or_else(|| eyre::eyre!("Package '{}' not found", name))?
-        .clone();
+    let runtime = DockerRuntime::connect()?;
+    delete_package_with_runtime(&runtime, name, include_images).await
+}
+
+pub(crate) async fn delete_package_with_runtime(
+    runtime: &impl ContainerRuntime,
+    name: &str,
+    include_images: bool,
+) -> Result<()> {
+    // A config that no longer matches the manifest must not stop the package being deleted
+    let config = PackageConfigStore::read(name)?;
+    let package = get_package(name, &config)?;
+
+    // Prometheus must leave the package network before it can be removed
+    if !package.metrics.is_empty() {
+        let installed = get_installed_packages_with_runtime(runtime).await?;
+        monitoring::disconnect_prometheus(runtime, &installed, &package.network_name).await?;
+    }
 
-    package::delete_package(&package, include_images).await?;
+    package::delete_package(runtime, &package, &kittynode_path()?, include_images).await?;
+    ImageLockStore::remove(name)?;
+    MetricsHistoryStore::remove(name)?;
     info!("Package '{}' deleted successfully.", name);
+
+    if !package.metrics.is_empty() {
+        let installed = get_installed_packages_with_runtime(runtime).await?;
+        if let Err(e) = monitoring::refresh_monitoring(runtime, &installed).await {
+            warn!("Failed to remove package '{}' from monitoring: {}", name, e);
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::install_package::install_package_with_runtime;
+    use crate::domain::package::PackageConfig;
+    use crate::infra::{fake_runtime::FakeRuntime, file::set_test_kittynode_path};
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn deletes_a_package_whose_config_has_an_unknown_key() {
+        let temp_dir = tempdir().unwrap();
+        set_test_kittynode_path(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        install_package_with_runtime(&runtime, "Ethereum", &|_| {})
+            .await
+            .unwrap();
+
+        let mut config = PackageConfig::new();
+        config
+            .values
+            .insert("removed_key".to_string(), "value".to_string());
+        PackageConfigStore::save("Ethereum", &config).unwrap();
+        let installed = get_installed_packages_with_runtime(&runtime).await.unwrap();
+        assert_eq!(installed.len(), 1);
+
+        delete_package_with_runtime(&runtime, "Ethereum", false)
+            .await
+            .unwrap();
+        assert!(runtime.state().containers.is_empty());
+        assert!(
+            get_installed_packages_with_runtime(&runtime)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
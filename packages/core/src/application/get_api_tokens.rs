@@ -0,0 +1,12 @@
+use crate::domain::auth::ApiTokenInfo;
+use crate::infra::auth::AuthStore;
+use eyre::Result;
+
+/// Returns the API tokens of the web server, without their secrets.
+pub fn get_api_tokens() -> Result<Vec<ApiTokenInfo>> {
+    Ok(AuthStore::load()?
+        .tokens
+        .iter()
+        .map(ApiTokenInfo::from)
+        .collect())
+}
@@ -1,10 +1,94 @@
-use crate::infra::docker::get_docker_instance;
+use crate::domain::logs::{LogLine, LogLineStream, LogsQuery};
+use crate::infra::{docker::DockerRuntime, runtime::ContainerRuntime};
 use eyre::Result;
+use tokio_stream::StreamExt;
 
-pub async fn get_container_logs(
+/// Returns the logs a container has written so far. `query.follow` is ignored.
+pub async fn get_container_logs(container_name: &str, query: &LogsQuery) -> Result<Vec<LogLine>> {
+    let runtime = DockerRuntime::connect()?;
+    get_container_logs_with_runtime(&runtime, container_name, query).await
+}
+
+/// Streams the logs of a container, following new lines when `query.follow` is set.
+pub fn stream_container_logs(container_name: &str, query: &LogsQuery) -> Result<LogLineStream> {
+    let runtime = DockerRuntime::connect()?;
+    Ok(runtime.container_logs(container_name, query))
+}
+
+pub(crate) async fn get_container_logs_with_runtime(
+    runtime: &impl ContainerRuntime,
     container_name: &str,
-    tail_lines: Option<usize>,
-) -> Result<Vec<String>> {
-    let docker = get_docker_instance()?;
-    crate::infra::docker::get_container_logs(&docker, container_name, tail_lines).await
+    query: &LogsQuery,
+) -> Result<Vec<LogLine>> {
+    let query = LogsQuery {
+        follow: false,
+        ..query.clone()
+    };
+    runtime
+        .container_logs(container_name, &query)
+        .collect::<Result<Vec<_>>>()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::container::Container;
+    use crate::domain::logs::LogSource;
+    use crate::infra::fake_runtime::FakeRuntime;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn filters_logs_by_stream_time_and_tail() {
+        let runtime = FakeRuntime::new();
+        let container = Container {
+            name: "reth-node".to_string(),
+            image: "reth".to_string(),
+            tag: "latest".to_string(),
+            digest: None,
+            cmd: Vec::new(),
+            port_bindings: HashMap::new(),
+            volume_bindings: Vec::new(),
+            file_bindings: Vec::new(),
+            ..Default::default()
+        };
+        runtime.pull_image("reth:latest", &|_| {}).await.unwrap();
+        runtime.create_container(&container).await.unwrap();
+        runtime.state().logs.insert(
+            "reth-node".to_string(),
+            vec![
+                LogLine::parse(LogSource::Stdout, "2025-01-01T00:00:00Z first"),
+                LogLine::parse(LogSource::Stderr, "2025-01-01T00:00:01Z warning"),
+                LogLine::parse(LogSource::Stdout, "2025-01-01T00:00:02Z second"),
+                LogLine::parse(LogSource::Stdout, "2025-01-01T00:00:03Z third"),
+            ],
+        );
+
+        let stdout = LogsQuery {
+            stderr: false,
+            ..Default::default()
+        };
+        let lines = get_container_logs_with_runtime(&runtime, "reth-node", &stdout)
+            .await
+            .unwrap();
+        assert_eq!(lines.len(), 3);
+
+        let query = LogsQuery {
+            tail: Some(2),
+            since: Some("2025-01-01T00:00:01Z".parse().unwrap()),
+            until: Some("2025-01-01T00:00:03Z".parse().unwrap()),
+            ..Default::default()
+        };
+        let lines = get_container_logs_with_runtime(&runtime, "reth-node", &query)
+            .await
+            .unwrap();
+        let messages: Vec<_> = lines.iter().map(|line| line.message.as_str()).collect();
+        assert_eq!(messages, ["warning", "second"]);
+
+        assert!(
+            get_container_logs_with_runtime(&runtime, "missing", &query)
+                .await
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,54 @@
+use crate::domain::node_status::EthereumNodeStatus;
+use crate::infra::{
+    node_status::{self, http_client},
+    package::get_package,
+    package_config::PackageConfigStore,
+};
+use eyre::{Result, eyre};
+
+pub(crate) const ETHEREUM_PACKAGE: &str = "Ethereum";
+const MEV_BOOST_CONTAINER: &str = "mev-boost";
+
+/// Returns the sync status of the Ethereum package, as reported by its clients, and the status
+/// of MEV-Boost and its relays when it is enabled.
+pub async fn get_ethereum_node_status() -> Result<EthereumNodeStatus> {
+    let config = PackageConfigStore::load(ETHEREUM_PACKAGE)?;
+    let package = get_package(ETHEREUM_PACKAGE, &config)?;
+    let endpoint = |name: &str| {
+        package
+            .endpoints
+            .get(name)
+            .ok_or_else(|| eyre!("Package '{}' has no '{}' endpoint", ETHEREUM_PACKAGE, name))
+    };
+
+    let client = http_client()?;
+    let mut status = node_status::get_ethereum_node_status(
+        &client,
+        endpoint("execution_rpc")?,
+        endpoint("consensus_api")?,
+    )
+    .await;
+
+    if package
+        .containers
+        .iter()
+        .any(|c| c.name == MEV_BOOST_CONTAINER)
+    {
+        let relays: Vec<_> = config
+            .values
+            .get("mev_boost_relays")
+            .map(|relays| {
+                relays
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|relay| !relay.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        status.mev_boost = Some(
+            node_status::get_mev_boost_status(&client, endpoint("mev_boost_api")?, &relays).await,
+        );
+    }
+    Ok(status)
+}
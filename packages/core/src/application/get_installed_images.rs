@@ -0,0 +1,8 @@
+use crate::domain::image::ImageLock;
+use crate::infra::image_lock::ImageLockStore;
+use eyre::Result;
+
+/// Returns the image tag and digest each container of an installed package was created from.
+pub fn get_installed_images(package_name: &str) -> Result<ImageLock> {
+    ImageLockStore::load(package_name)
+}
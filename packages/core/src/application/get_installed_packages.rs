@@ -1,13 +1,35 @@
 use crate::{
     domain::package::Package,
-    infra::package::{self, get_packages},
+    infra::{
+        docker::DockerRuntime,
+        package::{self, get_package, get_packages},
+        package_config::PackageConfigStore,
+        runtime::ContainerRuntime,
+    },
 };
 use eyre::{Context, Result};
+use std::collections::HashMap;
 use tracing::info;
 
 pub async fn get_installed_packages() -> Result<Vec<Package>> {
-    let packages = get_packages().wrap_err("Failed to retrieve packages")?;
-    let installed = package::get_installed_packages(&packages).await?;
+    let runtime = DockerRuntime::connect()?;
+    get_installed_packages_with_runtime(&runtime).await
+}
+
+pub(crate) async fn get_installed_packages_with_runtime(
+    runtime: &impl ContainerRuntime,
+) -> Result<Vec<Package>> {
+    // Render each package with its saved config, as that decides which containers it has. The
+    // config is not validated, so a manifest update that drops a key cannot hide the package
+    let mut packages = HashMap::new();
+    for name in get_packages()
+        .wrap_err("Failed to retrieve packages")?
+        .into_keys()
+    {
+        let config = PackageConfigStore::read(&name)?;
+        packages.insert(name.clone(), get_package(&name, &config)?);
+    }
+    let installed = package::get_installed_packages(runtime, &packages).await?;
     info!("Found {} installed packages", installed.len());
     Ok(installed)
 }
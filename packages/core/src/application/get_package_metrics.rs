@@ -0,0 +1,43 @@
+use crate::domain::metrics::{MetricsSnapshot, PackageMetrics};
+use crate::infra::{
+    metrics_history::MetricsHistoryStore, node_status::http_client, package::get_package,
+    package_config::PackageConfigStore, prometheus,
+};
+use chrono::{TimeDelta, Utc};
+use eyre::Result;
+use tracing::warn;
+
+/// Snapshots taken more often than this reuse the latest one.
+const MIN_SNAPSHOT_INTERVAL: TimeDelta = TimeDelta::seconds(5);
+/// The number of snapshots kept, an hour at one snapshot every 5 seconds.
+const MAX_SNAPSHOTS: usize = 720;
+
+/// Scrapes the key series of a package from its metrics endpoints and returns them along with
+/// the recent history, for charting.
+pub async fn get_package_metrics(name: &str) -> Result<PackageMetrics> {
+    let config = PackageConfigStore::load(name)?;
+    let package = get_package(name, &config)?;
+    // The history is only for charting, so a damaged one is started over
+    let mut snapshots = MetricsHistoryStore::load(name).unwrap_or_else(|e| {
+        warn!("Ignoring the metrics history of '{}': {}", name, e);
+        Vec::new()
+    });
+
+    let now = Utc::now();
+    if snapshots
+        .last()
+        .is_none_or(|last| now - last.timestamp >= MIN_SNAPSHOT_INTERVAL)
+    {
+        let jobs = prometheus::scrape_package(&http_client()?, &package).await;
+        let snapshot = MetricsSnapshot::new(now, jobs, snapshots.last());
+        snapshots.push(snapshot);
+        let excess = snapshots.len().saturating_sub(MAX_SNAPSHOTS);
+        snapshots.drain(..excess);
+        MetricsHistoryStore::save(name, &snapshots)?;
+    }
+
+    Ok(PackageMetrics {
+        package: name.to_string(),
+        snapshots,
+    })
+}
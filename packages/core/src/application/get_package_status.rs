@@ -0,0 +1,23 @@
+use crate::domain::package_status::PackageStatus;
+use crate::infra::{
+    docker::DockerRuntime,
+    package::{self, get_package},
+    package_config::PackageConfigStore,
+    runtime::ContainerRuntime,
+};
+use eyre::Result;
+
+/// Returns the state of a package and each of its containers.
+pub async fn get_package_status(name: &str) -> Result<PackageStatus> {
+    let runtime = DockerRuntime::connect()?;
+    get_package_status_with_runtime(&runtime, name).await
+}
+
+pub(crate) async fn get_package_status_with_runtime(
+    runtime: &impl ContainerRuntime,
+    name: &str,
+) -> Result<PackageStatus> {
+    let config = PackageConfigStore::load(name)?;
+    let package = get_package(name, &config)?;
+    package::get_package_status(runtime, &package).await
+}
@@ -0,0 +1,8 @@
+use crate::infra::config::ConfigStore;
+use eyre::Result;
+
+/// Returns the certificate fingerprint pinned for the remote server, empty when not paired.
+pub fn get_server_fingerprint() -> Result<String> {
+    let config = ConfigStore::load()?;
+    Ok(config.server_fingerprint)
+}
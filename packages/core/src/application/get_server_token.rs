@@ -0,0 +1,8 @@
+use crate::infra::config::ConfigStore;
+use eyre::Result;
+
+/// Returns the API token this app uses with the remote server, empty when not paired.
+pub fn get_server_token() -> Result<String> {
+    let config = ConfigStore::load()?;
+    Ok(config.server_token)
+}
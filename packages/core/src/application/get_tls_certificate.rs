@@ -0,0 +1,31 @@
+use crate::application::init_tls_certificate::init_tls_certificate;
+use crate::domain::tls::TlsCertificate;
+use crate::infra::{
+    config::ConfigStore,
+    tls::{TlsStore, check_certificate},
+};
+use eyre::Result;
+use std::path::PathBuf;
+
+/// Returns the certificate the web server serves, the user-supplied one if set. The
+/// self-signed certificate is generated if missing.
+pub fn get_tls_certificate() -> Result<TlsCertificate> {
+    let config = ConfigStore::load()?;
+    let self_signed = config.tls_cert_path.is_empty();
+    let (cert_path, key_path) = if self_signed {
+        init_tls_certificate()?;
+        (TlsStore::cert_path()?, TlsStore::key_path()?)
+    } else {
+        (
+            PathBuf::from(config.tls_cert_path),
+            PathBuf::from(config.tls_key_path),
+        )
+    };
+    let fingerprint = check_certificate(&cert_path, &key_path)?;
+    Ok(TlsCertificate {
+        cert_path: cert_path.display().to_string(),
+        key_path: key_path.display().to_string(),
+        fingerprint,
+        self_signed,
+    })
+}
@@ -0,0 +1,18 @@
+use crate::application::get_ethereum_node_status::ETHEREUM_PACKAGE;
+use crate::domain::validator::ValidatorKey;
+use crate::infra::{
+    keymanager::KeymanagerClient, package::get_package, package_config::PackageConfigStore,
+};
+use eyre::Result;
+
+/// Lists the validator keys loaded in the validator client of the Ethereum package.
+pub async fn get_validators() -> Result<Vec<ValidatorKey>> {
+    keymanager()?.list_keystores().await
+}
+
+/// Connects to the Keymanager API of the Ethereum package validator client.
+pub(crate) fn keymanager() -> Result<KeymanagerClient> {
+    let config = PackageConfigStore::load(ETHEREUM_PACKAGE)?;
+    let package = get_package(ETHEREUM_PACKAGE, &config)?;
+    KeymanagerClient::for_package(&package)
+}
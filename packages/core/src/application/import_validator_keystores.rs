@@ -0,0 +1,27 @@
+use crate::application::get_validators::keymanager;
+use crate::domain::validator::{ImportStatus, KeystoreImport, KeystoreImportResult};
+use crate::infra::validator_store::ValidatorStore;
+use eyre::Result;
+use tracing::info;
+
+/// Imports EIP-2335 keystores into the validator client of the Ethereum package.
+///
+/// Keys that ran in another validator client must come with its EIP-3076 slashing protection
+/// interchange. The password of each imported key is kept under `.kittynode`, readable only by
+/// the current user.
+pub async fn import_validator_keystores(
+    keystores: Vec<KeystoreImport>,
+    slashing_protection: Option<String>,
+) -> Result<Vec<KeystoreImportResult>> {
+    let results = keymanager()?
+        .import_keystores(&keystores, slashing_protection.as_deref())
+        .await?;
+
+    for (result, keystore) in results.iter().zip(&keystores) {
+        if result.status != ImportStatus::Error {
+            ValidatorStore::save_password(&result.pubkey, &keystore.password)?;
+        }
+        info!("Validator import {}", result);
+    }
+    Ok(results)
+}
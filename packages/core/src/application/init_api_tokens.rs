@@ -0,0 +1,26 @@
+use crate::domain::auth::{ADMIN_TOKEN_NAME, ApiToken, Scope};
+use crate::infra::{auth::AuthStore, file::generate_secret};
+use chrono::Utc;
+use eyre::Result;
+use tracing::info;
+
+/// Creates an admin token for the web server unless one exists, stored in
+/// `.kittynode/auth.toml`.
+pub fn init_api_tokens() -> Result<()> {
+    let created = AuthStore::update(|state| {
+        if state.tokens.iter().any(|token| token.scope == Scope::Admin) {
+            return false;
+        }
+        state.tokens.push(ApiToken {
+            name: ADMIN_TOKEN_NAME.to_string(),
+            scope: Scope::Admin,
+            token: generate_secret(""),
+            created_at: Utc::now(),
+        });
+        true
+    })?;
+    if created {
+        info!("Created the '{}' API token", ADMIN_TOKEN_NAME);
+    }
+    Ok(())
+}
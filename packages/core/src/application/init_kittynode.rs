@@ -1,10 +1,15 @@
+use crate::application::{
+    init_api_tokens::init_api_tokens, init_tls_certificate::init_tls_certificate,
+};
 use crate::domain::config::Config;
 use crate::infra::config::ConfigStore;
 use eyre::Result;
 
-/// Initializes Kittynode with the default config
+/// Initializes Kittynode with the default config and an admin token and certificate for the web server
 pub fn init_kittynode() -> Result<()> {
     let config = Config::default();
     ConfigStore::save(&config)?;
+    init_api_tokens()?;
+    init_tls_certificate()?;
     Ok(())
 }
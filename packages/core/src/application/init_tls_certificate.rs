@@ -0,0 +1,20 @@
+use crate::infra::tls::{TlsStore, generate_self_signed};
+use eyre::Result;
+use sysinfo::System;
+
+/// Generates a self-signed certificate for the web server unless one exists, stored in
+/// `.kittynode/tls`.
+pub fn init_tls_certificate() -> Result<()> {
+    let cert_path = TlsStore::cert_path()?;
+    let key_path = TlsStore::key_path()?;
+    if cert_path.exists() && key_path.exists() {
+        return Ok(());
+    }
+
+    let mut host_names = vec!["localhost".to_string(), "127.0.0.1".to_string()];
+    if let Some(host_name) = System::host_name() {
+        host_names.push(format!("{host_name}.local"));
+        host_names.push(host_name);
+    }
+    generate_self_signed(&cert_path, &key_path, host_names)
+}
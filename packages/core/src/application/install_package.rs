@@ -1,22 +1,58 @@
+use crate::application::get_installed_packages::get_installed_packages_with_runtime;
+use crate::domain::image::PullProgress;
 use crate::infra::{
-    file::generate_jwt_secret,
-    package::{self, get_packages},
+    docker::DockerRuntime,
+    file::kittynode_path,
+    image_lock::ImageLockStore,
+    monitoring,
+    package::{self, get_package},
     package_config::PackageConfigStore,
+    pull_progress::OnPullProgress,
+    runtime::ContainerRuntime,
 };
-use eyre::{Context, Result};
-use tracing::info;
+use eyre::Result;
+use tracing::{info, warn};
 
 pub async fn install_package(name: &str) -> Result<()> {
-    generate_jwt_secret().wrap_err("Failed to generate JWT secret")?;
+    install_package_with_progress(name, |_| {}).await
+}
 
-    let package = get_packages()?
-        .remove(name)
-        .ok_or_else(|| eyre::eyre!("Package '{}' not found", name))?;
+/// Installs a package, calling `on_progress` as each container image is pulled.
+pub async fn install_package_with_progress(
+    name: &str,
+    on_progress: impl Fn(&PullProgress) + Sync,
+) -> Result<()> {
+    let runtime = DockerRuntime::connect()?;
+    install_package_with_runtime(&runtime, name, &on_progress).await
+}
 
+pub(crate) async fn install_package_with_runtime(
+    runtime: &impl ContainerRuntime,
+    name: &str,
+    on_progress: OnPullProgress<'_>,
+) -> Result<()> {
     let config = PackageConfigStore::load(name)?;
-    let network = config.values.get("network");
+    let package = get_package(name, &config)?;
 
-    package::install_package(&package, network.map(String::as_str)).await?;
+    // A monitoring package starts out scraping the packages already installed
+    let mut scraped = Vec::new();
+    if let Some(monitoring) = &package.monitoring {
+        scraped = get_installed_packages_with_runtime(runtime).await?;
+        scraped.retain(|p| p.name != name);
+        monitoring::write_monitoring_config(monitoring, &scraped)?;
+    }
+
+    let lock = package::install_package(runtime, &package, &kittynode_path()?, on_progress).await?;
+    ImageLockStore::save(name, &lock)?;
     info!("Package '{}' installed successfully.", name);
+
+    if let Some(monitoring) = &package.monitoring {
+        monitoring::connect_prometheus(runtime, monitoring, &scraped).await?;
+    } else if !package.metrics.is_empty() {
+        let installed = get_installed_packages_with_runtime(runtime).await?;
+        if let Err(e) = monitoring::refresh_monitoring(runtime, &installed).await {
+            warn!("Failed to add package '{}' to monitoring: {}", name, e);
+        }
+    }
     Ok(())
 }
@@ -1,33 +1,89 @@
 pub mod add_capability;
+pub mod authorize_token;
+pub mod check_port_conflicts;
+pub mod check_requirements;
+pub mod create_pairing_code;
 pub mod delete_kittynode;
 pub mod delete_package;
+pub mod get_api_tokens;
 pub mod get_capabilities;
 pub mod get_container_logs;
+pub mod get_ethereum_node_status;
+pub mod get_installed_images;
 pub mod get_installed_packages;
 pub mod get_package_config;
+pub mod get_package_metrics;
+pub mod get_package_status;
 pub mod get_packages;
+pub mod get_server_fingerprint;
+pub mod get_server_token;
 pub mod get_server_url;
 pub mod get_system_info;
+pub mod get_tls_certificate;
+pub mod get_validators;
+pub mod import_validator_keystores;
+pub mod init_api_tokens;
 pub mod init_kittynode;
+pub mod init_tls_certificate;
 pub mod install_package;
 pub mod is_docker_running;
+pub mod pair_client;
 pub mod remove_capability;
+pub mod remove_validators;
+pub mod restart_package;
+pub mod revoke_api_token;
+pub mod set_server_fingerprint;
+pub mod set_server_token;
 pub mod set_server_url;
+pub mod set_tls_certificate;
+pub mod set_validator_fee_recipient;
+pub mod set_validator_graffiti;
+pub mod start_package;
+pub mod stop_package;
 pub mod update_package_config;
+pub mod upgrade_package;
 
 pub use add_capability::add_capability;
+pub use authorize_token::authorize_token;
+pub use check_port_conflicts::check_port_conflicts;
+pub use check_requirements::check_requirements;
+pub use create_pairing_code::create_pairing_code;
 pub use delete_kittynode::delete_kittynode;
 pub use delete_package::delete_package;
+pub use get_api_tokens::get_api_tokens;
 pub use get_capabilities::get_capabilities;
-pub use get_container_logs::get_container_logs;
+pub use get_container_logs::{get_container_logs, stream_container_logs};
+pub use get_ethereum_node_status::get_ethereum_node_status;
+pub use get_installed_images::get_installed_images;
 pub use get_installed_packages::get_installed_packages;
 pub use get_package_config::get_package_config;
+pub use get_package_metrics::get_package_metrics;
+pub use get_package_status::get_package_status;
 pub use get_packages::get_packages;
+pub use get_server_fingerprint::get_server_fingerprint;
+pub use get_server_token::get_server_token;
 pub use get_server_url::get_server_url;
 pub use get_system_info::get_system_info;
+pub use get_tls_certificate::get_tls_certificate;
+pub use get_validators::get_validators;
+pub use import_validator_keystores::import_validator_keystores;
+pub use init_api_tokens::init_api_tokens;
 pub use init_kittynode::init_kittynode;
-pub use install_package::install_package;
+pub use init_tls_certificate::init_tls_certificate;
+pub use install_package::{install_package, install_package_with_progress};
 pub use is_docker_running::is_docker_running;
+pub use pair_client::pair_client;
 pub use remove_capability::remove_capability;
+pub use remove_validators::remove_validators;
+pub use restart_package::restart_package;
+pub use revoke_api_token::revoke_api_token;
+pub use set_server_fingerprint::set_server_fingerprint;
+pub use set_server_token::set_server_token;
 pub use set_server_url::set_server_url;
+pub use set_tls_certificate::set_tls_certificate;
+pub use set_validator_fee_recipient::set_validator_fee_recipient;
+pub use set_validator_graffiti::set_validator_graffiti;
+pub use start_package::start_package;
+pub use stop_package::stop_package;
 pub use update_package_config::update_package_config;
+pub use upgrade_package::upgrade_package;
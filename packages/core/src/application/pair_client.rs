@@ -0,0 +1,17 @@
+use crate::domain::auth::ApiToken;
+use crate::infra::{auth::AuthStore, file::generate_secret};
+use chrono::Utc;
+use eyre::Result;
+use tracing::info;
+
+/// Exchanges a pairing code for a new API token, named after the client.
+pub fn pair_client(code: &str, name: &str) -> Result<ApiToken> {
+    // Expired codes are dropped even when pairing fails
+    let token =
+        AuthStore::update(|state| state.redeem(code, name, generate_secret(""), Utc::now()))??;
+    info!(
+        "Paired client '{}' with scope '{}'",
+        token.name, token.scope
+    );
+    Ok(token)
+}
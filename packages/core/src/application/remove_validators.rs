@@ -0,0 +1,36 @@
+use crate::application::get_validators::keymanager;
+use crate::domain::validator::{DeleteStatus, RemovedValidators};
+use crate::infra::validator_store::ValidatorStore;
+use eyre::{Context, Result};
+use tracing::info;
+
+/// Removes keys from the validator client of the Ethereum package.
+///
+/// The EIP-3076 slashing protection interchange of the keys is saved under `.kittynode` and
+/// returned, so the keys can safely be imported into another validator client.
+pub async fn remove_validators(pubkeys: Vec<String>) -> Result<RemovedValidators> {
+    let (results, slashing_protection) = keymanager()?.delete_keystores(&pubkeys).await?;
+    // The keys are already gone, so the interchange must not be lost with the error
+    let saved = ValidatorStore::save_slashing_protection(&slashing_protection);
+    let slashing_protection_path = saved.wrap_err_with(|| {
+        format!(
+            "Failed to save the slashing protection of the removed keys:\n{slashing_protection}"
+        )
+    })?;
+    info!(
+        "Saved the slashing protection interchange to {}",
+        slashing_protection_path.display()
+    );
+
+    for result in &results {
+        if result.status != DeleteStatus::Error {
+            ValidatorStore::remove_password(&result.pubkey)?;
+        }
+        info!("Validator removal {}", result);
+    }
+    Ok(RemovedValidators {
+        results,
+        slashing_protection,
+        slashing_protection_path: slashing_protection_path.display().to_string(),
+    })
+}
@@ -0,0 +1,17 @@
+use crate::infra::auth::AuthStore;
+use eyre::{Result, bail};
+use tracing::info;
+
+/// Revokes the API token of a paired client.
+pub fn revoke_api_token(name: &str) -> Result<()> {
+    let revoked = AuthStore::update(|state| {
+        let count = state.tokens.len();
+        state.tokens.retain(|token| token.name != name);
+        state.tokens.len() < count
+    })?;
+    if !revoked {
+        bail!("No API token named '{}'", name);
+    }
+    info!("Revoked API token '{}'", name);
+    Ok(())
+}
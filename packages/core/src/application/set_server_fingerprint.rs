@@ -0,0 +1,9 @@
+use crate::infra::config::ConfigStore;
+use eyre::Result;
+
+pub fn set_server_fingerprint(fingerprint: String) -> Result<()> {
+    let mut config = ConfigStore::load()?;
+    config.server_fingerprint = fingerprint;
+    ConfigStore::save(&config)?;
+    Ok(())
+}
@@ -0,0 +1,9 @@
+use crate::infra::config::ConfigStore;
+use eyre::Result;
+
+pub fn set_server_token(token: String) -> Result<()> {
+    let mut config = ConfigStore::load()?;
+    config.server_token = token;
+    ConfigStore::save(&config)?;
+    Ok(())
+}
@@ -3,6 +3,11 @@ use eyre::Result;
 
 pub fn set_server_url(endpoint: String) -> Result<()> {
     let mut config = ConfigStore::load()?;
+    // A token and pinned certificate only belong to the server they came from
+    if config.server_url != endpoint {
+        config.server_token.clear();
+        config.server_fingerprint.clear();
+    }
     config.server_url = endpoint;
     ConfigStore::save(&config)?;
     Ok(())
@@ -0,0 +1,31 @@
+use crate::application::get_tls_certificate::get_tls_certificate;
+use crate::domain::tls::TlsCertificate;
+use crate::infra::{config::ConfigStore, tls::check_certificate};
+use eyre::{Context, Result};
+use std::path::Path;
+
+/// Makes the web server serve a user-supplied PEM certificate and key, or the self-signed
+/// certificate again when `None`. Paired clients must pair again, as the fingerprint changes.
+pub fn set_tls_certificate(paths: Option<(&Path, &Path)>) -> Result<TlsCertificate> {
+    let mut config = ConfigStore::load()?;
+    match paths {
+        Some((cert_path, key_path)) => {
+            check_certificate(cert_path, key_path)?;
+            config.tls_cert_path = absolute(cert_path)?;
+            config.tls_key_path = absolute(key_path)?;
+        }
+        None => {
+            config.tls_cert_path.clear();
+            config.tls_key_path.clear();
+        }
+    }
+    ConfigStore::save(&config)?;
+    get_tls_certificate()
+}
+
+fn absolute(path: &Path) -> Result<String> {
+    Ok(std::path::absolute(path)
+        .wrap_err_with(|| format!("Failed to resolve {}", path.display()))?
+        .display()
+        .to_string())
+}
@@ -0,0 +1,7 @@
+use crate::application::get_validators::keymanager;
+use eyre::Result;
+
+/// Sets the address that receives the execution rewards of a validator.
+pub async fn set_validator_fee_recipient(pubkey: &str, address: &str) -> Result<()> {
+    keymanager()?.set_fee_recipient(pubkey, address).await
+}
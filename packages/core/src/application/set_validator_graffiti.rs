@@ -0,0 +1,7 @@
+use crate::application::get_validators::keymanager;
+use eyre::Result;
+
+/// Sets the graffiti included in the blocks a validator proposes.
+pub async fn set_validator_graffiti(pubkey: &str, graffiti: &str) -> Result<()> {
+    keymanager()?.set_graffiti(pubkey, graffiti).await
+}
@@ -0,0 +1,25 @@
+use crate::infra::{
+    docker::DockerRuntime,
+    package::{self, get_package},
+    package_config::PackageConfigStore,
+    runtime::ContainerRuntime,
+};
+use eyre::Result;
+use tracing::info;
+
+pub async fn stop_package(name: &str) -> Result<()> {
+    let runtime = DockerRuntime::connect()?;
+    stop_package_with_runtime(&runtime, name).await
+}
+
+pub(crate) async fn stop_package_with_runtime(
+    runtime: &impl ContainerRuntime,
+    name: &str,
+) -> Result<()> {
+    let config = PackageConfigStore::load(name)?;
+    let package = get_package(name, &config)?;
+
+    package::stop_package(runtime, &package).await?;
+    info!("Package '{}' stopped successfully.", name);
+    Ok(())
+}
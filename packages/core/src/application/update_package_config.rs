@@ -1,16 +1,62 @@
-use crate::application::delete_package;
-use crate::application::install_package;
+use crate::application::get_installed_packages::get_installed_packages_with_runtime;
 use crate::domain::package::PackageConfig;
-use crate::infra::package_config::PackageConfigStore;
+use crate::infra::{
+    docker::DockerRuntime,
+    file::kittynode_path,
+    image_lock::ImageLockStore,
+    manifest::ManifestStore,
+    monitoring,
+    package::{self, get_package},
+    package_config::PackageConfigStore,
+    runtime::ContainerRuntime,
+};
 use eyre::Result;
+use tracing::{info, warn};
 
 pub async fn update_package_config(package_name: &str, config: PackageConfig) -> Result<()> {
-    // Save the new configuration
-    PackageConfigStore::save(package_name, &config)?;
+    let runtime = DockerRuntime::connect()?;
+    update_package_config_with_runtime(&runtime, package_name, config).await
+}
+
+pub(crate) async fn update_package_config_with_runtime(
+    runtime: &impl ContainerRuntime,
+    package_name: &str,
+    config: PackageConfig,
+) -> Result<()> {
+    let schema = ManifestStore::load(package_name)?.config_schema();
+    schema.validate(package_name, &config)?;
 
-    // Restart the package with new configuration
-    delete_package(package_name, false).await?;
-    install_package(package_name).await?;
+    // Keys that do not affect the containers take effect without a reinstall
+    let current = PackageConfigStore::read(package_name)?;
+    if !schema.requires_recreate(&current, &config) {
+        PackageConfigStore::save(package_name, &config)?;
+        info!("Updated config of package '{}'", package_name);
+        return Ok(());
+    }
+
+    // Recreate the containers as installed with the previous configuration, keeping their data
+    let previous = get_package(package_name, &current)?;
+    let package = get_package(package_name, &config)?;
+    if !previous.metrics.is_empty() {
+        let installed = get_installed_packages_with_runtime(runtime).await?;
+        monitoring::disconnect_prometheus(runtime, &installed, &previous.network_name).await?;
+    }
+    let lock =
+        package::reconfigure_package(runtime, &previous, &package, &kittynode_path()?, &|_| {})
+            .await?;
+    PackageConfigStore::save(package_name, &config)?;
+    ImageLockStore::save(package_name, &lock)?;
+    info!("Updated config of package '{}'", package_name);
 
+    // A recreated Prometheus container is only connected to its own network
+    if package.monitoring.is_some() || !package.metrics.is_empty() {
+        let installed = get_installed_packages_with_runtime(runtime).await?;
+        if let Err(e) = monitoring::refresh_monitoring(runtime, &installed).await {
+            warn!(
+                "Failed to refresh monitoring of package '{}': {}",
+                package_name, e
+            );
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,43 @@
+use crate::application::get_installed_packages::get_installed_packages_with_runtime;
+use crate::infra::{
+    docker::DockerRuntime,
+    image_lock::ImageLockStore,
+    monitoring,
+    package::{self, get_package},
+    package_config::PackageConfigStore,
+    runtime::ContainerRuntime,
+};
+use eyre::Result;
+use std::time::Duration;
+use tracing::info;
+
+/// How long an upgraded container must stay running before the upgrade moves on.
+const HEALTH_CHECK_GRACE: Duration = Duration::from_secs(10);
+
+/// Upgrades an installed package to the image versions in its manifest and config, rolling back
+/// to the previously installed images if an upgraded container fails to start.
+pub async fn upgrade_package(name: &str) -> Result<()> {
+    let runtime = DockerRuntime::connect()?;
+    upgrade_package_with_runtime(&runtime, name).await
+}
+
+pub(crate) async fn upgrade_package_with_runtime(
+    runtime: &impl ContainerRuntime,
+    name: &str,
+) -> Result<()> {
+    let config = PackageConfigStore::load(name)?;
+    let package = get_package(name, &config)?;
+    let previous = ImageLockStore::load(name)?;
+
+    let lock =
+        package::upgrade_package(runtime, &package, &previous, HEALTH_CHECK_GRACE, &|_| {}).await?;
+    ImageLockStore::save(name, &lock)?;
+    info!("Package '{}' upgraded successfully.", name);
+
+    // A recreated Prometheus container is only connected to its own network
+    if package.monitoring.is_some() {
+        let installed = get_installed_packages_with_runtime(runtime).await?;
+        monitoring::refresh_monitoring(runtime, &installed).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use chrono::{DateTime, Utc};
+use eyre::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// The name of the admin token created for the web server, rather than paired by a client.
+pub const ADMIN_TOKEN_NAME: &str = "admin";
+
+/// What an API token of the web server may do. Each scope includes the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// Reading packages, status, metrics and logs, as monitoring clients do.
+    Read,
+    /// Everything, including installing and deleting packages.
+    Admin,
+}
+
+/// An API token of the web server.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiToken {
+    pub name: String,
+    pub scope: Scope,
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An API token without its secret, for listing.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiTokenInfo {
+    pub name: String,
+    pub scope: Scope,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A one-time code a client exchanges for an API token with the given scope.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PairingCode {
+    pub code: String,
+    pub scope: Scope,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A request to exchange a pairing code for an API token named after the client.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PairRequest {
+    pub code: String,
+    pub name: String,
+}
+
+/// The API tokens and pending pairing codes of the web server.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthState {
+    #[serde(default)]
+    pub tokens: Vec<ApiToken>,
+    #[serde(default)]
+    pub pairing_codes: Vec<PairingCode>,
+}
+
+impl Scope {
+    /// Returns whether a token with this scope may do what `required` allows.
+    #[must_use]
+    pub fn allows(self, required: Scope) -> bool {
+        self >= required
+    }
+}
+
+impl AuthState {
+    /// Returns the scope of a token, or `None` if it is not known.
+    #[must_use]
+    pub fn authorize(&self, token: &str) -> Option<Scope> {
+        self.tokens
+            .iter()
+            .find(|t| constant_time_eq(&t.token, token))
+            .map(|t| t.scope)
+    }
+
+    /// Consumes a pairing code, adding an API token with its scope. Expired codes are dropped.
+    pub fn redeem(
+        &mut self,
+        code: &str,
+        name: &str,
+        token: String,
+        now: DateTime<Utc>,
+    ) -> Result<ApiToken> {
+        self.pairing_codes
+            .retain(|pending| pending.expires_at > now);
+        let Some(index) = self
+            .pairing_codes
+            .iter()
+            .position(|pending| constant_time_eq(&pending.code, &normalize_code(code)))
+        else {
+            bail!("Invalid or expired pairing code");
+        };
+        if name.trim().is_empty() {
+            bail!("A client name is required to pair");
+        }
+        if self.tokens.iter().any(|t| t.name == name) {
+            bail!("A client named '{}' is already paired", name);
+        }
+
+        let pending = self.pairing_codes.remove(index);
+        let token = ApiToken {
+            name: name.to_string(),
+            scope: pending.scope,
+            token,
+            created_at: now,
+        };
+        self.tokens.push(token.clone());
+        Ok(token)
+    }
+}
+
+/// Uppercases a pairing code and drops separators, so `abcd-efgh` matches `ABCD-EFGH`.
+fn normalize_code(code: &str) -> String {
+    let code: String = code
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    match code.len() {
+        8 => format!("{}-{}", &code[..4], &code[4..]),
+        _ => code,
+    }
+}
+
+/// Compares secrets without returning early on the first difference.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+impl From<&ApiToken> for ApiTokenInfo {
+    fn from(token: &ApiToken) -> Self {
+        Self {
+            name: token.name.clone(),
+            scope: token.scope,
+            created_at: token.created_at,
+        }
+    }
+}
+
+impl FromStr for Scope {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "read" => Ok(Scope::Read),
+            "admin" => Ok(Scope::Admin),
+            _ => bail!("Invalid scope '{}', expected read or admin", s),
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::Read => write!(f, "read"),
+            Scope::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn redeems_a_pairing_code_once() {
+        let now = Utc::now();
+        let mut state = AuthState::default();
+        state.pairing_codes.push(PairingCode {
+            code: "ABCD-EFGH".to_string(),
+            scope: Scope::Read,
+            expires_at: now + TimeDelta::minutes(10),
+        });
+
+        assert!(state.redeem("WXYZ-WXYZ", "dash", "t1".into(), now).is_err());
+        let token = state.redeem("abcdefgh", "dash", "t1".into(), now).unwrap();
+        assert_eq!(token.scope, Scope::Read);
+        assert_eq!(state.authorize("t1"), Some(Scope::Read));
+        assert_eq!(state.authorize("t2"), None);
+        assert!(
+            state
+                .redeem("ABCD-EFGH", "other", "t2".into(), now)
+                .is_err()
+        );
+
+        state.pairing_codes.push(PairingCode {
+            code: "JKLM-NPQR".to_string(),
+            scope: Scope::Admin,
+            expires_at: now,
+        });
+        assert!(state.redeem("JKLM-NPQR", "late", "t3".into(), now).is_err());
+        assert!(state.pairing_codes.is_empty());
+
+        assert!(Scope::Admin.allows(Scope::Read));
+        assert!(!Scope::Read.allows(Scope::Admin));
+    }
+}
@@ -0,0 +1,135 @@
+use eyre::{Result, bail, eyre};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A condition on template variables, written as `key`, `!key`, `key == value` or
+/// `key != value`. A bare key holds when the variable is not empty. Conditions joined by `&&`
+/// hold when all of them do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    Set(String),
+    Unset(String),
+    Equals(String, String),
+    NotEquals(String, String),
+    All(Vec<Condition>),
+}
+
+impl Condition {
+    /// Evaluates the condition, failing if it references an unknown variable.
+    pub fn evaluate(&self, variables: &HashMap<String, String>) -> Result<bool> {
+        let lookup = |key: &str| {
+            variables
+                .get(key)
+                .ok_or_else(|| eyre!("Unknown variable '{}' in condition '{}'", key, self))
+        };
+        Ok(match self {
+            Condition::Set(key) => !lookup(key)?.is_empty(),
+            Condition::Unset(key) => lookup(key)?.is_empty(),
+            Condition::Equals(key, value) => lookup(key)? == value,
+            Condition::NotEquals(key, value) => lookup(key)? != value,
+            Condition::All(conditions) => {
+                for condition in conditions {
+                    if !condition.evaluate(variables)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+        })
+    }
+}
+
+impl FromStr for Condition {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.contains("&&") {
+            return s
+                .split("&&")
+                .map(str::parse)
+                .collect::<Result<_>>()
+                .map(Condition::All);
+        }
+
+        let valid_key = |key: &str| {
+            !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        };
+
+        let condition = if let Some((key, value)) = s.split_once("!=") {
+            Condition::NotEquals(key.trim().to_string(), value.trim().to_string())
+        } else if let Some((key, value)) = s.split_once("==") {
+            Condition::Equals(key.trim().to_string(), value.trim().to_string())
+        } else if let Some(key) = s.trim().strip_prefix('!') {
+            Condition::Unset(key.trim().to_string())
+        } else {
+            Condition::Set(s.trim().to_string())
+        };
+
+        let (Condition::Set(key)
+        | Condition::Unset(key)
+        | Condition::Equals(key, _)
+        | Condition::NotEquals(key, _)) = &condition
+        else {
+            unreachable!("conditions joined by && are parsed above");
+        };
+        if !valid_key(key) {
+            bail!("Invalid condition '{}'", s);
+        }
+        Ok(condition)
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Set(key) => write!(f, "{key}"),
+            Condition::Unset(key) => write!(f, "!{key}"),
+            Condition::Equals(key, value) => write!(f, "{key} == {value}"),
+            Condition::NotEquals(key, value) => write!(f, "{key} != {value}"),
+            Condition::All(conditions) => {
+                let conditions: Vec<_> = conditions.iter().map(ToString::to_string).collect();
+                write!(f, "{}", conditions.join(" && "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_evaluates_conditions() {
+        let variables = HashMap::from([
+            ("network".to_string(), "custom".to_string()),
+            ("bootnodes".to_string(), String::new()),
+        ]);
+        let evaluate = |condition: &str| {
+            condition
+                .parse::<Condition>()
+                .unwrap()
+                .evaluate(&variables)
+                .unwrap()
+        };
+
+        assert!(evaluate("network == custom"));
+        assert!(!evaluate("network != custom"));
+        assert!(evaluate("network"));
+        assert!(evaluate("!bootnodes"));
+        assert!(!evaluate("bootnodes"));
+        assert!(evaluate("network == custom && !bootnodes"));
+        assert!(!evaluate("network == custom && bootnodes"));
+        assert!(
+            "netwrok"
+                .parse::<Condition>()
+                .unwrap()
+                .evaluate(&variables)
+                .is_err()
+        );
+        assert!("== custom".parse::<Condition>().is_err());
+    }
+}
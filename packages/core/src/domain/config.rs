@@ -4,4 +4,16 @@ use serde::{Deserialize, Serialize};
 pub struct Config {
     pub capabilities: Vec<String>,
     pub server_url: String,
+    /// The API token of the server at `server_url`, from pairing.
+    #[serde(default)]
+    pub server_token: String,
+    /// The certificate fingerprint of the server at `server_url`, pinned on pairing.
+    #[serde(default)]
+    pub server_fingerprint: String,
+    /// A certificate for the web server to use instead of the self-signed one.
+    #[serde(default)]
+    pub tls_cert_path: String,
+    /// The private key of `tls_cert_path`.
+    #[serde(default)]
+    pub tls_key_path: String,
 }
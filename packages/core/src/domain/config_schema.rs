@@ -0,0 +1,307 @@
+use crate::domain::condition::Condition;
+use crate::domain::container::{parse_cpus, parse_memory};
+use crate::domain::package::PackageConfig;
+use crate::domain::validator::is_hex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigType {
+    #[default]
+    String,
+    Integer,
+    Boolean,
+    /// A TCP or UDP port number from 1 to 65535.
+    Port,
+    /// An Ethereum address, or empty when unset.
+    Address,
+    /// A memory size such as `16g`, or empty when unlimited.
+    Memory,
+    /// A number of CPUs such as `4` or `1.5`, or empty when unlimited.
+    Cpus,
+}
+
+/// A config key a package reads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ConfigField {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub kind: ConfigType,
+    /// The values the key accepts. Any value of the type is accepted when empty.
+    pub allowed: Vec<String>,
+    pub default: String,
+    pub description: String,
+    /// A condition on other config keys under which the key must not be empty.
+    pub required_when: Option<String>,
+    /// A condition on config keys under which the value of the key is not supported.
+    pub invalid_when: Option<String>,
+    /// Whether changing the value requires the package containers to be recreated.
+    pub recreate: bool,
+}
+
+/// The config keys a package reads, in the order they should be presented.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ConfigSchema {
+    pub fields: Vec<ConfigField>,
+}
+
+/// A config value that does not match the schema of its package.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ConfigFieldError {
+    pub key: String,
+    pub message: String,
+}
+
+/// The error returned when a package config does not match its schema.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigValidationError {
+    pub package: String,
+    pub errors: Vec<ConfigFieldError>,
+}
+
+impl ConfigField {
+    /// Checks a value against the type and allowed values of the field.
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        let valid_type = match self.kind {
+            ConfigType::String => true,
+            ConfigType::Integer => value.parse::<i64>().is_ok(),
+            ConfigType::Boolean => matches!(value, "true" | "false"),
+            ConfigType::Port => value.parse::<u16>().is_ok_and(|port| port != 0),
+            ConfigType::Address => value.is_empty() || is_hex(value, 40),
+            ConfigType::Memory => parse_memory(value).is_ok(),
+            ConfigType::Cpus => parse_cpus(value).is_ok(),
+        };
+        if !valid_type {
+            return Err(format!("'{value}' is not a valid {}", self.kind));
+        }
+        if !self.allowed.is_empty() && !self.allowed.iter().any(|allowed| allowed == value) {
+            return Err(format!(
+                "'{value}' is not allowed, expected one of: {}",
+                self.allowed.join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ConfigSchema {
+    #[must_use]
+    pub fn field(&self, key: &str) -> Option<&ConfigField> {
+        self.fields.iter().find(|field| field.key == key)
+    }
+
+    /// Checks every value of a config, reporting unknown keys and invalid values by key.
+    pub fn validate(
+        &self,
+        package: &str,
+        config: &PackageConfig,
+    ) -> Result<(), ConfigValidationError> {
+        let mut keys: Vec<_> = config.values.keys().collect();
+        keys.sort();
+
+        let mut errors = Vec::new();
+        for key in keys {
+            let result = match self.field(key) {
+                Some(field) => field.check(&config.values[key]),
+                None => Err("Unknown config key".to_string()),
+            };
+            if let Err(message) = result {
+                errors.push(ConfigFieldError {
+                    key: key.clone(),
+                    message,
+                });
+            }
+        }
+
+        let values: HashMap<_, _> = self
+            .fields
+            .iter()
+            .map(|field| {
+                let value = config.values.get(&field.key).unwrap_or(&field.default);
+                (field.key.clone(), value.clone())
+            })
+            .collect();
+        let holds = |condition: &str| {
+            condition
+                .parse::<Condition>()
+                .and_then(|condition| condition.evaluate(&values))
+                .unwrap_or(false)
+        };
+        for field in &self.fields {
+            let message = if let Some(required_when) = field
+                .required_when
+                .as_deref()
+                .filter(|condition| values[&field.key].is_empty() && holds(condition))
+            {
+                format!("Required when {required_when}")
+            } else if let Some(invalid_when) = field
+                .invalid_when
+                .as_deref()
+                .filter(|condition| holds(condition))
+            {
+                format!("Not supported when {invalid_when}")
+            } else {
+                continue;
+            };
+            if !errors.iter().any(|e| e.key == field.key) {
+                errors.push(ConfigFieldError {
+                    key: field.key.clone(),
+                    message,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigValidationError {
+                package: package.to_string(),
+                errors,
+            })
+        }
+    }
+
+    /// Returns whether going from one config to another changes a key that requires the
+    /// containers to be recreated. Unset keys take their default.
+    #[must_use]
+    pub fn requires_recreate(&self, old: &PackageConfig, new: &PackageConfig) -> bool {
+        self.fields.iter().any(|field| {
+            let value = |config: &PackageConfig| {
+                config
+                    .values
+                    .get(&field.key)
+                    .cloned()
+                    .unwrap_or_else(|| field.default.clone())
+            };
+            field.recreate && value(old) != value(new)
+        })
+    }
+}
+
+impl fmt::Display for ConfigType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            ConfigType::String => "string",
+            ConfigType::Integer => "integer",
+            ConfigType::Boolean => "boolean",
+            ConfigType::Port => "port",
+            ConfigType::Address => "address",
+            ConfigType::Memory => "memory size",
+            ConfigType::Cpus => "number of CPUs",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+impl fmt::Display for ConfigValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid config for package '{}':", self.package)?;
+        for error in &self.errors {
+            write!(f, "\n- {}: {}", error.key, error.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigValidationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> ConfigSchema {
+        ConfigSchema {
+            fields: vec![
+                ConfigField {
+                    key: "network".to_string(),
+                    kind: ConfigType::String,
+                    allowed: vec!["mainnet".to_string(), "holesky".to_string()],
+                    default: "holesky".to_string(),
+                    description: String::new(),
+                    required_when: None,
+                    invalid_when: None,
+                    recreate: true,
+                },
+                ConfigField {
+                    key: "rpc_port".to_string(),
+                    kind: ConfigType::Port,
+                    allowed: Vec::new(),
+                    default: "8545".to_string(),
+                    description: String::new(),
+                    required_when: None,
+                    invalid_when: None,
+                    recreate: true,
+                },
+                ConfigField {
+                    key: "custom_dir".to_string(),
+                    kind: ConfigType::String,
+                    allowed: Vec::new(),
+                    default: String::new(),
+                    description: String::new(),
+                    required_when: Some("network == mainnet".to_string()),
+                    invalid_when: None,
+                    recreate: true,
+                },
+                ConfigField {
+                    key: "label".to_string(),
+                    kind: ConfigType::String,
+                    allowed: Vec::new(),
+                    default: String::new(),
+                    description: String::new(),
+                    required_when: None,
+                    invalid_when: None,
+                    recreate: false,
+                },
+            ],
+        }
+    }
+
+    fn config(values: &[(&str, &str)]) -> PackageConfig {
+        PackageConfig {
+            values: values
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn reports_field_errors() {
+        let schema = schema();
+        assert!(
+            schema
+                .validate("Ethereum", &config(&[("rpc_port", "30303")]))
+                .is_ok()
+        );
+        let error = schema
+            .validate("Ethereum", &config(&[("network", "mainnet")]))
+            .unwrap_err();
+        assert_eq!(error.errors[0].key, "custom_dir");
+
+        let error = schema
+            .validate(
+                "Ethereum",
+                &config(&[
+                    ("netwrok", "mainnet"),
+                    ("network", "foo"),
+                    ("rpc_port", "0"),
+                ]),
+            )
+            .unwrap_err();
+        let keys: Vec<_> = error.errors.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["network", "netwrok", "rpc_port"]);
+        assert_eq!(error.errors[1].message, "Unknown config key");
+    }
+
+    #[test]
+    fn only_recreate_keys_require_recreate() {
+        let schema = schema();
+        let old = config(&[("label", "a")]);
+        assert!(!schema.requires_recreate(&old, &config(&[("label", "b")])));
+        assert!(!schema.requires_recreate(&old, &config(&[("network", "holesky")])));
+        assert!(schema.requires_recreate(&old, &config(&[("network", "mainnet")])));
+    }
+}
@@ -1,28 +1,214 @@
 use bollard::models::PortBinding;
+use eyre::{Result, bail};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct Container {
     pub(crate) name: String,
     pub(crate) image: String,
+    pub(crate) tag: String,
+    pub(crate) digest: Option<String>,
     pub(crate) cmd: Vec<String>,
+    #[schema(value_type = HashMap<String, Vec<Object>>)]
     pub(crate) port_bindings: HashMap<String, Vec<PortBinding>>,
     pub(crate) volume_bindings: Vec<Binding>,
     pub(crate) file_bindings: Vec<Binding>,
+    #[serde(default)]
+    pub(crate) restart_policy: RestartPolicy,
+    #[serde(default)]
+    pub(crate) limits: ResourceLimits,
+    #[serde(default)]
+    pub(crate) ulimits: Vec<Ulimit>,
+    /// The signal that asks the container to exit, such as `SIGINT`.
+    #[serde(default)]
+    pub(crate) stop_signal: Option<String>,
+    /// Seconds Docker waits for the container to exit before killing it.
+    #[serde(default)]
+    pub(crate) stop_timeout_secs: Option<i64>,
+    #[serde(default)]
+    pub(crate) log_config: Option<LogConfig>,
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+/// When Docker restarts a container that exits, named as in `docker run --restart`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    #[default]
+    No,
+    Always,
+    UnlessStopped,
+    OnFailure,
+}
+
+/// Caps on the resources of a container. A limit is not applied when unset.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ResourceLimits {
+    pub(crate) memory_bytes: Option<i64>,
+    /// CPU time in units of 10^-9 CPUs, as Docker expects.
+    pub(crate) nano_cpus: Option<i64>,
+}
+
+/// A process resource limit, such as the number of open files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Ulimit {
+    pub(crate) name: String,
+    pub(crate) soft: i64,
+    pub(crate) hard: i64,
+}
+
+/// The Docker logging driver of a container and its options, such as `max-size`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LogConfig {
+    pub(crate) driver: String,
+    pub(crate) options: HashMap<String, String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
 pub struct Binding {
     pub(crate) source: String,
     pub(crate) destination: String,
     pub(crate) options: Option<String>,
+    /// Whether the source is kept when the package is deleted.
+    #[serde(default)]
+    pub(crate) persist: bool,
+}
+
+impl Container {
+    /// Returns the reference the image is pulled and run by, pinned to the digest when known.
+    #[must_use]
+    pub fn image_reference(&self) -> String {
+        match &self.digest {
+            Some(digest) => format!("{}@{}", self.image, digest),
+            None => format!("{}:{}", self.image, self.tag),
+        }
+    }
+}
+
+impl RestartPolicy {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RestartPolicy::No => "no",
+            RestartPolicy::Always => "always",
+            RestartPolicy::UnlessStopped => "unless-stopped",
+            RestartPolicy::OnFailure => "on-failure",
+        }
+    }
+}
+
+impl FromStr for RestartPolicy {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "no" => Ok(RestartPolicy::No),
+            "always" => Ok(RestartPolicy::Always),
+            "unless-stopped" => Ok(RestartPolicy::UnlessStopped),
+            "on-failure" => Ok(RestartPolicy::OnFailure),
+            _ => bail!(
+                "Invalid restart policy '{}', expected no, always, unless-stopped or on-failure",
+                s
+            ),
+        }
+    }
+}
+
+/// Parses a memory size such as `512m` or `16g` into bytes, or `None` when empty.
+pub fn parse_memory(value: &str) -> Result<Option<i64>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let lower = value.to_ascii_lowercase();
+    let (number, unit) = match lower.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => lower.split_at(i),
+        None => (lower.as_str(), ""),
+    };
+    let multiplier: i64 = match unit {
+        "" | "b" => 1,
+        "k" | "kb" => 1 << 10,
+        "m" | "mb" => 1 << 20,
+        "g" | "gb" => 1 << 30,
+        _ => bail!(
+            "Invalid memory size '{}', expected for example 512m or 16g",
+            value
+        ),
+    };
+    match number
+        .parse::<i64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+    {
+        Some(bytes) if bytes > 0 => Ok(Some(bytes)),
+        _ => bail!(
+            "Invalid memory size '{}', expected for example 512m or 16g",
+            value
+        ),
+    }
+}
+
+/// Parses a number of CPUs such as `2` or `1.5` into nano CPUs, or `None` when empty.
+pub fn parse_cpus(value: &str) -> Result<Option<i64>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<f64>() {
+        Ok(cpus) if cpus.is_finite() && cpus > 0.0 && cpus <= 1024.0 => {
+            Ok(Some((cpus * 1e9).round() as i64))
+        }
+        _ => bail!(
+            "Invalid number of CPUs '{}', expected for example 2 or 1.5",
+            value
+        ),
+    }
 }
 
 impl fmt::Display for Container {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "- Name: {}", self.name)?;
-        writeln!(f, "  Image: {}", self.image)
+        writeln!(f, "  Image: {}:{}", self.image, self.tag)?;
+        if let Some(digest) = &self.digest {
+            writeln!(f, "  Digest: {digest}")?;
+        }
+        if self.restart_policy != RestartPolicy::No {
+            writeln!(f, "  Restart policy: {}", self.restart_policy.as_str())?;
+        }
+        if let Some(bytes) = self.limits.memory_bytes {
+            writeln!(f, "  Memory limit: {} MiB", bytes >> 20)?;
+        }
+        if let Some(nano_cpus) = self.limits.nano_cpus {
+            writeln!(f, "  CPU limit: {}", nano_cpus as f64 / 1e9)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_resource_limits() {
+        assert_eq!(parse_memory("").unwrap(), None);
+        assert_eq!(parse_memory("512m").unwrap(), Some(512 << 20));
+        assert_eq!(parse_memory("16G").unwrap(), Some(16 << 30));
+        assert_eq!(parse_memory("1024").unwrap(), Some(1024));
+        assert!(parse_memory("16t").is_err());
+        assert!(parse_memory("0m").is_err());
+
+        assert_eq!(parse_cpus(" ").unwrap(), None);
+        assert_eq!(parse_cpus("1.5").unwrap(), Some(1_500_000_000));
+        assert!(parse_cpus("-1").is_err());
+
+        assert_eq!(
+            "unless-stopped".parse::<RestartPolicy>().unwrap(),
+            RestartPolicy::UnlessStopped
+        );
+        assert!("sometimes".parse::<RestartPolicy>().is_err());
     }
 }
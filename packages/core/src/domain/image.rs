@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use utoipa::ToSchema;
+
+/// The image a container was created from, as resolved at pull time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct InstalledImage {
+    pub image: String,
+    pub tag: String,
+    pub digest: Option<String>,
+}
+
+/// The images recorded for each container of an installed package, keyed by container name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ImageLock {
+    pub containers: BTreeMap<String, InstalledImage>,
+}
+
+/// The progress of an image pull, summed over the layers the engine has reported so far.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PullProgress {
+    /// The image reference being pulled.
+    pub reference: String,
+    pub downloaded_bytes: u64,
+    /// The size of the layers being downloaded, growing as the engine discovers more layers.
+    pub total_bytes: u64,
+    pub layers_done: usize,
+    pub layers_total: usize,
+    /// Whether the pull has finished.
+    pub done: bool,
+}
+
+impl InstalledImage {
+    /// Returns the most precise reference for the image, preferring the digest over the tag.
+    #[must_use]
+    pub fn reference(&self) -> String {
+        match &self.digest {
+            Some(digest) => format!("{}@{}", self.image, digest),
+            None => format!("{}:{}", self.image, self.tag),
+        }
+    }
+}
+
+impl fmt::Display for InstalledImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.image, self.tag)?;
+        if let Some(digest) = &self.digest {
+            write!(f, " ({digest})")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PullProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}/{} layers, {:.1}/{:.1} MB",
+            self.reference,
+            self.layers_done,
+            self.layers_total,
+            self.downloaded_bytes as f64 / 1_000_000.0,
+            self.total_bytes as f64 / 1_000_000.0
+        )?;
+        if self.done {
+            write!(f, " (done)")?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,119 @@
+use chrono::{DateTime, Utc};
+use eyre::Result;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::pin::Pin;
+use tokio_stream::Stream;
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A stream of container log lines. When following, it ends once the container stops.
+pub type LogLineStream = Pin<Box<dyn Stream<Item = Result<LogLine>> + Send>>;
+
+fn default_true() -> bool {
+    true
+}
+
+/// Which container logs to read.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct LogsQuery {
+    /// Only return the last `tail` lines written before the query.
     pub tail: Option<usize>,
+    /// Keep the stream open and yield new lines as the container writes them.
+    #[serde(default)]
+    pub follow: bool,
+    /// Only return lines written at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only return lines written before this time.
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default = "default_true")]
+    pub stdout: bool,
+    #[serde(default = "default_true")]
+    pub stderr: bool,
+}
+
+impl Default for LogsQuery {
+    fn default() -> Self {
+        Self {
+            tail: None,
+            follow: false,
+            since: None,
+            until: None,
+            stdout: true,
+            stderr: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LogSource {
+    Stdout,
+    Stderr,
+}
+
+/// A single line written by a container.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LogLine {
+    pub source: LogSource,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub message: String,
+}
+
+impl LogLine {
+    /// Parses a raw log line, which may be prefixed with an RFC 3339 timestamp and a space.
+    #[must_use]
+    pub fn parse(source: LogSource, raw: &str) -> Self {
+        let raw = raw.trim_end_matches(['\r', '\n']);
+        let parsed = raw.split_once(' ').and_then(|(timestamp, message)| {
+            DateTime::parse_from_rfc3339(timestamp)
+                .ok()
+                .map(|timestamp| (timestamp.with_timezone(&Utc), message))
+        });
+
+        match parsed {
+            Some((timestamp, message)) => Self {
+                source,
+                timestamp: Some(timestamp),
+                message: message.to_string(),
+            },
+            None => Self {
+                source,
+                timestamp: None,
+                message: raw.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for LogLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.timestamp {
+            Some(timestamp) => write!(f, "{} {}", timestamp.to_rfc3339(), self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_timestamped_lines() {
+        let line = LogLine::parse(
+            LogSource::Stderr,
+            "2025-03-20T10:15:30.123456789Z INFO Syncing\n",
+        );
+        assert_eq!(line.source, LogSource::Stderr);
+        assert_eq!(
+            line.timestamp,
+            Some("2025-03-20T10:15:30.123456789Z".parse().unwrap())
+        );
+        assert_eq!(line.message, "INFO Syncing");
+
+        let line = LogLine::parse(LogSource::Stdout, "no timestamp here");
+        assert_eq!(line.timestamp, None);
+        assert_eq!(line.message, "no timestamp here");
+    }
 }
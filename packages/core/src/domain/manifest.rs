@@ -0,0 +1,413 @@
+use crate::domain::condition::Condition;
+use crate::domain::config_schema::{ConfigField, ConfigSchema, ConfigType};
+use crate::domain::metrics::{MetricSeries, Selector};
+use eyre::{Result, WrapErr, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// The manifest schema version understood by this release of `kittynode-core`.
+pub const MANIFEST_SCHEMA_VERSION: u32 = 1;
+
+/// A declarative description of a package, loaded from a TOML manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackageManifest {
+    pub(crate) schema_version: u32,
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) network_name: String,
+    #[serde(default)]
+    pub(crate) config: BTreeMap<String, ConfigKeyManifest>,
+    #[serde(default)]
+    pub(crate) containers: Vec<ContainerManifest>,
+    /// Host reachable URLs of the APIs the package serves, keyed by name.
+    #[serde(default)]
+    pub(crate) endpoints: BTreeMap<String, String>,
+    #[serde(default)]
+    pub(crate) requirements: RequirementsManifest,
+    /// Random tokens generated at install time, keyed by name.
+    #[serde(default)]
+    pub(crate) secrets: BTreeMap<String, SecretManifest>,
+    /// Prometheus metrics endpoints the containers serve on the package network.
+    #[serde(default)]
+    pub(crate) metrics: Vec<MetricsManifest>,
+    /// Set by packages that scrape the metrics of the other installed packages.
+    pub(crate) monitoring: Option<MonitoringManifest>,
+}
+
+/// A config key a package reads, with its type and default value.
+///
+/// When `values` is not empty, the key only accepts those values, and each value may carry
+/// attributes which templates reference as `{{key.attribute}}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigKeyManifest {
+    #[serde(default, rename = "type")]
+    pub(crate) kind: ConfigType,
+    pub(crate) default: String,
+    #[serde(default)]
+    pub(crate) description: String,
+    /// A condition on other config keys under which the key must not be empty.
+    pub(crate) required_when: Option<String>,
+    /// A condition on config keys under which the value of the key is not supported.
+    pub(crate) invalid_when: Option<String>,
+    /// Whether changing the key requires the containers to be recreated, which is the default.
+    #[serde(default = "default_recreate")]
+    pub(crate) recreate: bool,
+    #[serde(default)]
+    pub(crate) values: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+fn default_recreate() -> bool {
+    true
+}
+
+/// A random token the package reads from a file, such as an API token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SecretManifest {
+    pub(crate) path: String,
+    /// Text written before the random hex digits.
+    #[serde(default)]
+    pub(crate) prefix: String,
+    /// A condition under which the secret is generated, see [`Condition`].
+    pub(crate) when: Option<String>,
+}
+
+/// A Prometheus metrics endpoint served by a container of the package. The endpoint is left out
+/// when the container is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsManifest {
+    /// The scrape job name, which dashboards select metrics by.
+    pub(crate) job: String,
+    pub(crate) container: String,
+    pub(crate) port: u16,
+    #[serde(default = "default_metrics_path")]
+    pub(crate) path: String,
+    /// A host reachable URL of the endpoint, without the path, for Kittynode to scrape the key
+    /// series from.
+    pub(crate) host_url: Option<String>,
+    #[serde(default)]
+    pub(crate) series: MetricSeries,
+}
+
+fn default_metrics_path() -> String {
+    "/metrics".to_string()
+}
+
+/// Where Kittynode writes the Prometheus and Grafana config of a monitoring package.
+///
+/// The config is generated from the metrics endpoints of the installed packages, and Prometheus
+/// is connected to their networks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MonitoringManifest {
+    pub(crate) prometheus_container: String,
+    pub(crate) config_dir: String,
+}
+
+/// The host resources a package needs.
+///
+/// Values are templates, so they can depend on config, for example
+/// `min_disk_gb = "{{network.min_disk_gb}}"`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RequirementsManifest {
+    pub(crate) min_cores: Option<String>,
+    pub(crate) recommended_cores: Option<String>,
+    pub(crate) min_memory_gb: Option<String>,
+    pub(crate) recommended_memory_gb: Option<String>,
+    pub(crate) min_disk_gb: Option<String>,
+    pub(crate) recommended_disk_gb: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContainerManifest {
+    pub(crate) name: String,
+    /// A condition under which the container is part of the package, see [`Condition`].
+    pub(crate) when: Option<String>,
+    pub(crate) image: String,
+    /// The pinned image tag, usually a config key so operators can override it.
+    pub(crate) tag: String,
+    /// An optional digest the image must match, for example `sha256:...`.
+    pub(crate) digest: Option<String>,
+    #[serde(default)]
+    pub(crate) cmd: Vec<ArgManifest>,
+    #[serde(default)]
+    pub(crate) ports: Vec<PortManifest>,
+    #[serde(default)]
+    pub(crate) volumes: Vec<BindingManifest>,
+    #[serde(default)]
+    pub(crate) files: Vec<BindingManifest>,
+    /// The restart policy, such as `unless-stopped`, usually a config key.
+    pub(crate) restart: Option<String>,
+    #[serde(default)]
+    pub(crate) limits: LimitsManifest,
+    #[serde(default)]
+    pub(crate) ulimits: Vec<UlimitManifest>,
+    /// The signal the container is stopped with, such as `SIGINT`. Docker sends `SIGTERM`
+    /// when unset.
+    pub(crate) stop_signal: Option<String>,
+    /// Seconds to wait for the container to exit on stop before it is killed.
+    pub(crate) stop_timeout: Option<u32>,
+    pub(crate) log: Option<LogManifest>,
+}
+
+/// Resource limits, usually config keys. An empty limit is not applied.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsManifest {
+    /// A memory size such as `16g`.
+    pub(crate) memory: Option<String>,
+    /// A number of CPUs such as `4` or `1.5`.
+    pub(crate) cpus: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UlimitManifest {
+    pub(crate) name: String,
+    pub(crate) soft: i64,
+    pub(crate) hard: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LogManifest {
+    pub(crate) driver: String,
+    /// Options of the logging driver, such as `max-size`. Values may use config keys.
+    #[serde(default)]
+    pub(crate) options: BTreeMap<String, String>,
+}
+
+/// A command line argument, or a group of arguments only passed when a condition holds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ArgManifest {
+    Arg(String),
+    Group(ArgGroupManifest),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ArgGroupManifest {
+    pub(crate) args: Vec<String>,
+    /// A condition such as `network == custom`, see [`Condition`].
+    pub(crate) when: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PortManifest {
+    /// The container port and protocol, for example `30303/udp`.
+    pub(crate) container_port: String,
+    pub(crate) host_ip: String,
+    pub(crate) host_port: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BindingManifest {
+    pub(crate) source: String,
+    pub(crate) destination: String,
+    pub(crate) options: Option<String>,
+    /// A condition under which the binding is created, see [`Condition`].
+    pub(crate) when: Option<String>,
+    /// Whether the source is kept when the package is deleted, for data that must outlive the
+    /// package such as validator keys.
+    #[serde(default)]
+    pub(crate) persist: bool,
+}
+
+impl PackageManifest {
+    /// Returns the schema of the config keys the package reads.
+    pub(crate) fn config_schema(&self) -> ConfigSchema {
+        ConfigSchema {
+            fields: self
+                .config
+                .iter()
+                .map(|(key, entry)| ConfigField {
+                    key: key.clone(),
+                    kind: entry.kind,
+                    allowed: entry.values.keys().cloned().collect(),
+                    default: entry.default.clone(),
+                    description: entry.description.clone(),
+                    required_when: entry.required_when.clone(),
+                    invalid_when: entry.invalid_when.clone(),
+                    recreate: entry.recreate,
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks the manifest for structural errors that would otherwise surface at install time.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.schema_version != MANIFEST_SCHEMA_VERSION {
+            bail!(
+                "Manifest '{}' uses schema version {}, but only version {} is supported",
+                self.name,
+                self.schema_version,
+                MANIFEST_SCHEMA_VERSION
+            );
+        }
+        if self.name.trim().is_empty() {
+            bail!("Manifest name must not be empty");
+        }
+        if self.network_name.trim().is_empty() {
+            bail!("Manifest '{}' must declare a network name", self.name);
+        }
+        if self.containers.is_empty() {
+            bail!(
+                "Manifest '{}' must declare at least one container",
+                self.name
+            );
+        }
+
+        let schema = self.config_schema();
+        let defaults = schema
+            .fields
+            .iter()
+            .map(|field| (field.key.clone(), field.default.clone()))
+            .collect();
+        for field in &schema.fields {
+            if let Err(message) = field.check(&field.default) {
+                bail!(
+                    "Manifest '{}' config key '{}' has an invalid default: {}",
+                    self.name,
+                    field.key,
+                    message
+                );
+            }
+            for condition in [&field.required_when, &field.invalid_when]
+                .into_iter()
+                .flatten()
+            {
+                condition
+                    .parse::<Condition>()
+                    .and_then(|condition| condition.evaluate(&defaults))
+                    .wrap_err_with(|| {
+                        format!(
+                            "Manifest '{}' config key '{}' has an invalid condition",
+                            self.name, field.key
+                        )
+                    })?;
+            }
+        }
+
+        let mut names = HashSet::new();
+        for container in &self.containers {
+            if !names.insert(container.name.as_str()) {
+                bail!(
+                    "Manifest '{}' declares container '{}' more than once",
+                    self.name,
+                    container.name
+                );
+            }
+            for port in &container.ports {
+                validate_container_port(&self.name, &container.name, &port.container_port)?;
+            }
+            if let Some(signal) = &container.stop_signal
+                && (signal.is_empty()
+                    || !signal
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-'))
+            {
+                bail!(
+                    "Manifest '{}' container '{}' has invalid stop signal '{}'",
+                    self.name,
+                    container.name,
+                    signal
+                );
+            }
+            for ulimit in &container.ulimits {
+                if ulimit.name.trim().is_empty() || ulimit.soft > ulimit.hard {
+                    bail!(
+                        "Manifest '{}' container '{}' has invalid ulimit '{}', the soft limit must not exceed the hard limit",
+                        self.name,
+                        container.name,
+                        ulimit.name
+                    );
+                }
+            }
+            let conditions = container
+                .cmd
+                .iter()
+                .filter_map(|arg| match arg {
+                    ArgManifest::Arg(_) => None,
+                    ArgManifest::Group(group) => Some(&group.when),
+                })
+                .chain(&container.when)
+                .chain(
+                    self.secrets
+                        .values()
+                        .filter_map(|secret| secret.when.as_ref()),
+                )
+                .chain(
+                    container
+                        .volumes
+                        .iter()
+                        .chain(&container.files)
+                        .filter_map(|binding| binding.when.as_ref()),
+                );
+            for condition in conditions {
+                condition.parse::<Condition>().wrap_err_with(|| {
+                    format!(
+                        "Manifest '{}' container '{}' has an invalid condition",
+                        self.name, container.name
+                    )
+                })?;
+            }
+        }
+
+        let monitoring_container = self
+            .monitoring
+            .as_ref()
+            .map(|monitoring| &monitoring.prometheus_container);
+        for container in self
+            .metrics
+            .iter()
+            .map(|metrics| &metrics.container)
+            .chain(monitoring_container)
+        {
+            if !names.contains(container.as_str()) {
+                bail!(
+                    "Manifest '{}' references unknown container '{}'",
+                    self.name,
+                    container
+                );
+            }
+        }
+        for metrics in &self.metrics {
+            for selector in metrics.series.selectors() {
+                selector.parse::<Selector>().wrap_err_with(|| {
+                    format!(
+                        "Manifest '{}' metrics job '{}' has an invalid series",
+                        self.name, metrics.job
+                    )
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_container_port(manifest: &str, container: &str, port: &str) -> Result<()> {
+    let valid = match port.split_once('/') {
+        Some((number, protocol)) => {
+            number.parse::<u16>().is_ok() && matches!(protocol, "tcp" | "udp")
+        }
+        None => false,
+    };
+    if !valid {
+        bail!(
+            "Manifest '{}' container '{}' has invalid port '{}', expected '<port>/<tcp|udp>'",
+            manifest,
+            container,
+            port
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use chrono::{DateTime, Utc};
+use eyre::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Selectors for the key series of a metrics endpoint, such as `libp2p_peers` or
+/// `reth_sync_checkpoint{stage="Finish"}`. Each reads the sum of the matching samples.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MetricSeries {
+    pub sync_height: Option<String>,
+    pub peers: Option<String>,
+    /// A counter of the CPU time used, in seconds.
+    pub cpu_seconds: Option<String>,
+    pub disk_bytes: Option<String>,
+}
+
+/// A sample selector: a metric name with optional `label="value"` matchers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    pub name: String,
+    pub labels: Vec<(String, String)>,
+}
+
+/// The key series read from one metrics endpoint of a package.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct JobMetrics {
+    pub job: String,
+    pub sync_height: Option<f64>,
+    pub peers: Option<f64>,
+    pub cpu_seconds: Option<f64>,
+    /// CPU usage since the previous snapshot, where 100 is one full core.
+    pub cpu_percent: Option<f64>,
+    pub disk_bytes: Option<f64>,
+    /// Why the endpoint could not be scraped.
+    pub error: Option<String>,
+}
+
+/// The key series of every metrics endpoint of a package at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MetricsSnapshot {
+    pub timestamp: DateTime<Utc>,
+    pub jobs: Vec<JobMetrics>,
+}
+
+/// The recent metrics history of a package, oldest snapshot first.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PackageMetrics {
+    pub package: String,
+    pub snapshots: Vec<MetricsSnapshot>,
+}
+
+impl MetricSeries {
+    /// Returns every selector, for validation.
+    pub(crate) fn selectors(&self) -> impl Iterator<Item = &String> {
+        [
+            &self.sync_height,
+            &self.peers,
+            &self.cpu_seconds,
+            &self.disk_bytes,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+impl Selector {
+    #[must_use]
+    pub fn matches(&self, name: &str, labels: &[(String, String)]) -> bool {
+        self.name == name
+            && self
+                .labels
+                .iter()
+                .all(|matcher| labels.iter().any(|label| label == matcher))
+    }
+}
+
+impl MetricsSnapshot {
+    /// Creates a snapshot, deriving CPU usage from the CPU time in the previous snapshot.
+    #[must_use]
+    pub fn new(
+        timestamp: DateTime<Utc>,
+        mut jobs: Vec<JobMetrics>,
+        previous: Option<&MetricsSnapshot>,
+    ) -> Self {
+        if let Some(previous) = previous {
+            let elapsed = (timestamp - previous.timestamp).num_milliseconds() as f64 / 1000.0;
+            for job in &mut jobs {
+                let previous_cpu = previous
+                    .jobs
+                    .iter()
+                    .find(|p| p.job == job.job)
+                    .and_then(|p| p.cpu_seconds);
+                // A counter that went down belongs to a restarted process
+                if let (Some(cpu), Some(previous_cpu)) = (job.cpu_seconds, previous_cpu)
+                    && elapsed > 0.0
+                    && cpu >= previous_cpu
+                {
+                    job.cpu_percent = Some((cpu - previous_cpu) / elapsed * 100.0);
+                }
+            }
+        }
+        Self { timestamp, jobs }
+    }
+}
+
+impl FromStr for Selector {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, matchers) = match s.trim().split_once('{') {
+            Some((name, rest)) => match rest.strip_suffix('}') {
+                Some(matchers) => (name, matchers),
+                None => bail!("Invalid selector '{}', expected a closing brace", s),
+            },
+            None => (s.trim(), ""),
+        };
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+        {
+            bail!("Invalid metric name in selector '{}'", s);
+        }
+
+        let mut labels = Vec::new();
+        for matcher in matchers.split(',').filter(|m| !m.trim().is_empty()) {
+            let Some((label, value)) = matcher.split_once('=') else {
+                bail!("Invalid label matcher '{}' in selector '{}'", matcher, s);
+            };
+            let Some(value) = value
+                .trim()
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+            else {
+                bail!("Label values must be quoted in selector '{}'", s);
+            };
+            labels.push((label.trim().to_string(), value.to_string()));
+        }
+
+        Ok(Selector {
+            name: name.to_string(),
+            labels,
+        })
+    }
+}
+
+impl fmt::Display for PackageMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(latest) = self.snapshots.last() else {
+            return writeln!(f, "No metrics recorded for package '{}'", self.package);
+        };
+        writeln!(
+            f,
+            "Metrics of package '{}' at {}:",
+            self.package, latest.timestamp
+        )?;
+        for job in &latest.jobs {
+            writeln!(f, "{}:", job.job)?;
+            if let Some(error) = &job.error {
+                writeln!(f, "  Unreachable: {error}")?;
+                continue;
+            }
+            let values = [
+                ("Sync height", job.sync_height),
+                ("Peers", job.peers),
+                (
+                    "CPU %",
+                    job.cpu_percent.map(|cpu| (cpu * 10.0).round() / 10.0),
+                ),
+                (
+                    "Disk GB",
+                    job.disk_bytes.map(|bytes| (bytes / 1e8).round() / 10.0),
+                ),
+            ];
+            for (label, value) in values {
+                match value {
+                    Some(value) => writeln!(f, "  {label}: {value}")?,
+                    None => writeln!(f, "  {label}: unknown")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn derives_cpu_percent_from_the_previous_snapshot() {
+        let job = |cpu_seconds| JobMetrics {
+            job: "reth".to_string(),
+            cpu_seconds: Some(cpu_seconds),
+            ..Default::default()
+        };
+        let start = Utc::now();
+        let first = MetricsSnapshot::new(start, vec![job(100.0)], None);
+        assert_eq!(first.jobs[0].cpu_percent, None);
+
+        let second = MetricsSnapshot::new(
+            start + TimeDelta::seconds(10),
+            vec![job(105.0)],
+            Some(&first),
+        );
+        assert_eq!(second.jobs[0].cpu_percent, Some(50.0));
+
+        let restarted = MetricsSnapshot::new(
+            start + TimeDelta::seconds(20),
+            vec![job(1.0)],
+            Some(&second),
+        );
+        assert_eq!(restarted.jobs[0].cpu_percent, None);
+
+        let selector: Selector = r#"reth_sync_checkpoint{stage="Finish"}"#.parse().unwrap();
+        assert!(selector.matches(
+            "reth_sync_checkpoint",
+            &[("stage".to_string(), "Finish".to_string())]
+        ));
+        assert!(!selector.matches("reth_sync_checkpoint", &[]));
+        assert!(
+            "reth_sync_checkpoint{stage=Finish}"
+                .parse::<Selector>()
+                .is_err()
+        );
+    }
+}
@@ -1,5 +1,17 @@
+pub mod auth;
+pub mod condition;
 pub mod config;
+pub mod config_schema;
 pub mod container;
+pub mod image;
 pub mod logs;
+pub mod manifest;
+pub mod metrics;
+pub mod node_status;
 pub mod package;
+pub mod package_status;
+pub mod ports;
+pub mod requirements;
 pub mod system_info;
+pub mod tls;
+pub mod validator;
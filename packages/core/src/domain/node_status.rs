@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// The status of an Ethereum node, as reported by its execution and consensus clients.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EthereumNodeStatus {
+    pub execution: ExecutionClientStatus,
+    pub consensus: ConsensusClientStatus,
+    /// Whether the consensus client is following a reachable, non-optimistic execution client.
+    pub clients_in_agreement: bool,
+    /// The status of MEV-Boost and its relays, when MEV-Boost is enabled.
+    pub mev_boost: Option<MevBoostStatus>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionClientStatus {
+    pub reachable: bool,
+    pub is_syncing: bool,
+    pub current_block: Option<u64>,
+    pub highest_block: Option<u64>,
+    pub peers: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsensusHealth {
+    Ready,
+    Syncing,
+    NotInitialized,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConsensusClientStatus {
+    pub reachable: bool,
+    pub health: ConsensusHealth,
+    pub head_slot: Option<u64>,
+    pub sync_distance: Option<u64>,
+    pub is_syncing: bool,
+    pub is_optimistic: bool,
+    /// Whether the consensus client reports its execution client as offline.
+    pub el_offline: Option<bool>,
+    pub peers: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MevBoostStatus {
+    /// Whether MEV-Boost answered its status endpoint. It fails when no relay is reachable.
+    pub reachable: bool,
+    pub relays: Vec<RelayStatus>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RelayStatus {
+    pub url: String,
+    pub reachable: bool,
+    pub error: Option<String>,
+}
+
+impl Default for ConsensusClientStatus {
+    fn default() -> Self {
+        Self {
+            reachable: false,
+            health: ConsensusHealth::Unknown,
+            head_slot: None,
+            sync_distance: None,
+            is_syncing: false,
+            is_optimistic: false,
+            el_offline: None,
+            peers: None,
+            error: None,
+        }
+    }
+}
+
+impl EthereumNodeStatus {
+    #[must_use]
+    pub fn new(execution: ExecutionClientStatus, consensus: ConsensusClientStatus) -> Self {
+        let clients_in_agreement = execution.reachable
+            && consensus.reachable
+            && consensus.el_offline == Some(false)
+            && !consensus.is_optimistic;
+        Self {
+            execution,
+            consensus,
+            clients_in_agreement,
+            mev_boost: None,
+        }
+    }
+}
+
+fn write_optional<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    value: Option<T>,
+) -> fmt::Result {
+    match value {
+        Some(value) => writeln!(f, "  {label}: {value}"),
+        None => writeln!(f, "  {label}: unknown"),
+    }
+}
+
+impl fmt::Display for EthereumNodeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Execution client:")?;
+        match &self.execution.error {
+            Some(error) => writeln!(f, "  Unreachable: {error}")?,
+            None => {
+                writeln!(f, "  Syncing: {}", self.execution.is_syncing)?;
+                write_optional(f, "Current block", self.execution.current_block)?;
+                write_optional(f, "Highest block", self.execution.highest_block)?;
+                write_optional(f, "Peers", self.execution.peers)?;
+            }
+        }
+
+        writeln!(f, "Consensus client:")?;
+        match &self.consensus.error {
+            Some(error) => writeln!(f, "  Unreachable: {error}")?,
+            None => {
+                writeln!(f, "  Health: {:?}", self.consensus.health)?;
+                writeln!(f, "  Syncing: {}", self.consensus.is_syncing)?;
+                write_optional(f, "Head slot", self.consensus.head_slot)?;
+                write_optional(f, "Sync distance", self.consensus.sync_distance)?;
+                write_optional(f, "Peers", self.consensus.peers)?;
+            }
+        }
+
+        writeln!(f, "Clients in agreement: {}", self.clients_in_agreement)?;
+
+        if let Some(mev_boost) = &self.mev_boost {
+            writeln!(f, "MEV-Boost:")?;
+            match &mev_boost.error {
+                Some(error) => writeln!(f, "  Unavailable: {error}")?,
+                None => writeln!(f, "  Available")?,
+            }
+            for relay in &mev_boost.relays {
+                match &relay.error {
+                    Some(error) => writeln!(f, "  Relay {}: unreachable ({error})", relay.url)?,
+                    None => writeln!(f, "  Relay {}: reachable", relay.url)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,13 @@
+use crate::domain::config_schema::ConfigSchema;
 use crate::domain::container::Container;
-use eyre::Result;
+use crate::domain::metrics::MetricSeries;
+use crate::domain::requirements::Requirements;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
+use utoipa::{IntoParams, ToSchema};
 
-pub(crate) trait PackageDefinition {
-    const NAME: &'static str;
-    fn get_package() -> Result<Package>;
-}
-
-#[derive(Clone, Serialize, Deserialize, Default)]
+#[derive(Clone, Serialize, Deserialize, Default, ToSchema)]
 pub struct PackageConfig {
     pub values: HashMap<String, String>,
 }
@@ -23,13 +21,56 @@ impl PackageConfig {
     }
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+/// A random token written to a file on the host when the package is installed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Secret {
+    pub(crate) path: String,
+    pub(crate) prefix: String,
+}
+
+/// A Prometheus metrics endpoint, reachable from the package network and optionally from the
+/// host.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct MetricsEndpoint {
+    pub(crate) job: String,
+    pub(crate) container: String,
+    pub(crate) port: u16,
+    pub(crate) path: String,
+    pub(crate) host_url: Option<String>,
+    pub(crate) series: MetricSeries,
+}
+
+/// Where a monitoring package keeps the Prometheus and Grafana config generated for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Monitoring {
+    pub(crate) prometheus_container: String,
+    pub(crate) config_dir: String,
+}
+
+/// How to delete a package.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteOptions {
+    /// Also remove the images of its containers.
+    #[serde(default)]
+    pub include_images: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
 pub struct Package {
     pub(crate) name: String,
     pub(crate) description: String,
     pub(crate) network_name: String,
     pub(crate) containers: Vec<Container>,
+    /// Host reachable URLs of the APIs the package serves, keyed by name.
+    pub(crate) endpoints: HashMap<String, String>,
+    pub(crate) requirements: Requirements,
+    /// Random tokens generated at install time, keyed by name.
+    pub(crate) secrets: HashMap<String, Secret>,
+    pub(crate) metrics: Vec<MetricsEndpoint>,
+    pub(crate) monitoring: Option<Monitoring>,
     pub(crate) default_config: PackageConfig,
+    pub(crate) config_schema: ConfigSchema,
 }
 
 impl fmt::Display for Package {
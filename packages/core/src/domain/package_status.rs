@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// A container restarted at least this many times, and up for less than
+/// `CRASH_LOOP_MIN_UPTIME_SECS`, is considered to be crash-looping.
+const CRASH_LOOP_MIN_RESTARTS: u64 = 3;
+const CRASH_LOOP_MIN_UPTIME_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerState {
+    Created,
+    Running,
+    Paused,
+    Restarting,
+    Removing,
+    Exited,
+    Dead,
+    /// The container does not exist.
+    Missing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ContainerStatus {
+    pub name: String,
+    pub state: ContainerState,
+    /// The health check status reported by the runtime, such as `healthy` or `unhealthy`.
+    pub health: Option<String>,
+    pub exit_code: Option<i64>,
+    pub uptime_secs: Option<u64>,
+    pub restart_count: u64,
+    pub image_digest: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageState {
+    /// None of the package containers exist.
+    NotInstalled,
+    /// Some, but not all, of the package containers exist.
+    PartiallyInstalled,
+    /// Every container is running.
+    Running,
+    /// Every container exists, but none are running.
+    Stopped,
+    /// Some containers are running and others are not.
+    Degraded,
+    /// At least one container keeps restarting.
+    CrashLooping,
+    /// At least one container is failing its health check.
+    Unhealthy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PackageStatus {
+    pub name: String,
+    pub state: PackageState,
+    pub containers: Vec<ContainerStatus>,
+}
+
+impl ContainerStatus {
+    /// Returns the status of a container that does not exist.
+    #[must_use]
+    pub fn missing(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: ContainerState::Missing,
+            health: None,
+            exit_code: None,
+            uptime_secs: None,
+            restart_count: 0,
+            image_digest: None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_crash_looping(&self) -> bool {
+        self.state == ContainerState::Restarting
+            || (self.restart_count >= CRASH_LOOP_MIN_RESTARTS
+                && self
+                    .uptime_secs
+                    .is_none_or(|uptime| uptime < CRASH_LOOP_MIN_UPTIME_SECS))
+    }
+}
+
+impl PackageStatus {
+    /// Derives the overall package state from the status of its containers.
+    #[must_use]
+    pub fn from_containers(name: &str, containers: Vec<ContainerStatus>) -> Self {
+        let count = |state| containers.iter().filter(|c| c.state == state).count();
+        let missing = count(ContainerState::Missing);
+        let running = count(ContainerState::Running);
+
+        let state = if missing == containers.len() {
+            PackageState::NotInstalled
+        } else if missing > 0 {
+            PackageState::PartiallyInstalled
+        } else if containers.iter().any(ContainerStatus::is_crash_looping) {
+            PackageState::CrashLooping
+        } else if containers
+            .iter()
+            .any(|c| c.health.as_deref() == Some("unhealthy"))
+        {
+            PackageState::Unhealthy
+        } else if running == containers.len() {
+            PackageState::Running
+        } else if running == 0 {
+            PackageState::Stopped
+        } else {
+            PackageState::Degraded
+        };
+
+        Self {
+            name: name.to_string(),
+            state,
+            containers,
+        }
+    }
+}
+
+impl fmt::Display for ContainerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self {
+            ContainerState::Created => "created",
+            ContainerState::Running => "running",
+            ContainerState::Paused => "paused",
+            ContainerState::Restarting => "restarting",
+            ContainerState::Removing => "removing",
+            ContainerState::Exited => "exited",
+            ContainerState::Dead => "dead",
+            ContainerState::Missing => "missing",
+        };
+        write!(f, "{state}")
+    }
+}
+
+impl fmt::Display for PackageState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self {
+            PackageState::NotInstalled => "not installed",
+            PackageState::PartiallyInstalled => "partially installed",
+            PackageState::Running => "running",
+            PackageState::Stopped => "installed but stopped",
+            PackageState::Degraded => "degraded",
+            PackageState::CrashLooping => "crash-looping",
+            PackageState::Unhealthy => "unhealthy",
+        };
+        write!(f, "{state}")
+    }
+}
+
+impl fmt::Display for PackageStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Package: {}", self.name)?;
+        writeln!(f, "Status: {}", self.state)?;
+        writeln!(f, "Containers:")?;
+        for container in &self.containers {
+            writeln!(f, "- Name: {}", container.name)?;
+            write!(f, "  State: {}", container.state)?;
+            if let Some(health) = &container.health {
+                write!(f, " ({health})")?;
+            }
+            writeln!(f)?;
+            if let Some(exit_code) = container.exit_code {
+                writeln!(f, "  Exit code: {exit_code}")?;
+            }
+            if let Some(uptime) = container.uptime_secs {
+                writeln!(f, "  Uptime: {uptime}s")?;
+            }
+            writeln!(f, "  Restarts: {}", container.restart_count)?;
+            if let Some(digest) = &container.image_digest {
+                writeln!(f, "  Image digest: {digest}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(name: &str, state: ContainerState) -> ContainerStatus {
+        ContainerStatus {
+            state,
+            uptime_secs: Some(3600),
+            ..ContainerStatus::missing(name)
+        }
+    }
+
+    #[test]
+    fn derives_package_state() {
+        let cases = [
+            (
+                vec![ContainerState::Missing, ContainerState::Missing],
+                PackageState::NotInstalled,
+            ),
+            (
+                vec![ContainerState::Running, ContainerState::Missing],
+                PackageState::PartiallyInstalled,
+            ),
+            (
+                vec![ContainerState::Running, ContainerState::Running],
+                PackageState::Running,
+            ),
+            (
+                vec![ContainerState::Exited, ContainerState::Created],
+                PackageState::Stopped,
+            ),
+            (
+                vec![ContainerState::Running, ContainerState::Exited],
+                PackageState::Degraded,
+            ),
+            (
+                vec![ContainerState::Running, ContainerState::Restarting],
+                PackageState::CrashLooping,
+            ),
+        ];
+
+        for (states, expected) in cases {
+            let containers = states
+                .iter()
+                .enumerate()
+                .map(|(i, state)| status(&format!("c{i}"), *state))
+                .collect();
+            let package = PackageStatus::from_containers("Test", containers);
+            assert_eq!(package.state, expected, "{states:?}");
+        }
+    }
+
+    #[test]
+    fn detects_crash_loops_and_unhealthy_containers() {
+        let mut flapping = status("c0", ContainerState::Running);
+        flapping.restart_count = 5;
+        flapping.uptime_secs = Some(4);
+        let package = PackageStatus::from_containers("Test", vec![flapping]);
+        assert_eq!(package.state, PackageState::CrashLooping);
+
+        let mut unhealthy = status("c0", ContainerState::Running);
+        unhealthy.health = Some("unhealthy".to_string());
+        let package = PackageStatus::from_containers("Test", vec![unhealthy]);
+        assert_eq!(package.state, PackageState::Unhealthy);
+    }
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// The host port the Kittynode web server listens on, which package defaults must leave free.
+pub const WEB_SERVER_PORT: u16 = 3000;
+
+/// A host port published by a container.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PublishedPort {
+    pub container: String,
+    pub host_ip: String,
+    pub host_port: u16,
+    /// The transport protocol, `tcp` or `udp`.
+    pub protocol: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum PortConflictSource {
+    /// A process on the host is already listening on the port.
+    HostListener,
+    /// Another container already publishes the port.
+    Container { name: String },
+    /// Another container of the same package binds the port.
+    Package { container: String },
+}
+
+/// A host port a package container binds which is already taken.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PortConflict {
+    pub container: String,
+    pub host_ip: String,
+    pub host_port: u16,
+    pub protocol: String,
+    pub source: PortConflictSource,
+}
+
+/// The error returned when a package cannot be installed because its host ports are taken.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PortConflictError {
+    pub package: String,
+    pub conflicts: Vec<PortConflict>,
+}
+
+impl PublishedPort {
+    /// Returns whether two bindings of the same port and protocol would collide, which they do
+    /// unless both are bound to different specific addresses.
+    #[must_use]
+    pub fn overlaps(&self, other: &PublishedPort) -> bool {
+        let wildcard = |ip: &str| ip.is_empty() || ip == "0.0.0.0" || ip == "::";
+        self.host_port == other.host_port
+            && self.protocol == other.protocol
+            && (wildcard(&self.host_ip)
+                || wildcard(&other.host_ip)
+                || self.host_ip == other.host_ip)
+    }
+}
+
+impl fmt::Display for PortConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}/{} for container '{}' is already used by ",
+            self.host_ip, self.host_port, self.protocol, self.container
+        )?;
+        match &self.source {
+            PortConflictSource::HostListener => write!(f, "a process on the host"),
+            PortConflictSource::Container { name } => write!(f, "container '{name}'"),
+            PortConflictSource::Package { container } => {
+                write!(f, "container '{container}' of the same package")
+            }
+        }
+    }
+}
+
+impl fmt::Display for PortConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Package '{}' has port conflicts:", self.package)?;
+        for conflict in &self.conflicts {
+            write!(f, "\n- {conflict}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PortConflictError {}
@@ -0,0 +1,287 @@
+use crate::domain::system_info::{DiskInfo, SystemInfo};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+const BYTES_PER_GB: u64 = 1_000_000_000;
+
+/// The host resources a package needs. Unset values are not checked.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Requirements {
+    pub min_cores: Option<u32>,
+    pub recommended_cores: Option<u32>,
+    pub min_memory_gb: Option<u64>,
+    pub recommended_memory_gb: Option<u64>,
+    /// Free space needed on the disk backing the container engine's data root.
+    pub min_disk_gb: Option<u64>,
+    pub recommended_disk_gb: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Resource {
+    Cores,
+    Memory,
+    Disk,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    /// The host meets the minimum but not the recommendation, or could not be checked.
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RequirementFinding {
+    pub resource: Resource,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RequirementsReport {
+    pub package: String,
+    pub findings: Vec<RequirementFinding>,
+}
+
+impl RequirementsReport {
+    /// Compares the requirements of a package with the host.
+    ///
+    /// `data_root` is the directory the container engine stores images and volumes in. Its disk
+    /// is the mounted disk with the longest mount point that contains it.
+    #[must_use]
+    pub fn check(
+        package: &str,
+        requirements: &Requirements,
+        system: &SystemInfo,
+        data_root: Option<&str>,
+    ) -> Self {
+        let mut findings = Vec::new();
+
+        if requirements.min_cores.is_some() || requirements.recommended_cores.is_some() {
+            findings.push(compare(
+                Resource::Cores,
+                u64::from(system.processor.cores),
+                requirements.min_cores.map(u64::from),
+                requirements.recommended_cores.map(u64::from),
+                |n| format!("{n} cores"),
+            ));
+        }
+
+        if requirements.min_memory_gb.is_some() || requirements.recommended_memory_gb.is_some() {
+            findings.push(compare(
+                Resource::Memory,
+                system.memory.total_bytes,
+                requirements.min_memory_gb.map(|gb| gb * BYTES_PER_GB),
+                requirements
+                    .recommended_memory_gb
+                    .map(|gb| gb * BYTES_PER_GB),
+                |bytes| format!("{} GB of memory", bytes / BYTES_PER_GB),
+            ));
+        }
+
+        if requirements.min_disk_gb.is_some() || requirements.recommended_disk_gb.is_some() {
+            let disk = data_root.and_then(|data_root| find_disk(&system.storage.disks, data_root));
+            findings.push(match disk {
+                Some(disk) => {
+                    let mut finding = compare(
+                        Resource::Disk,
+                        disk.available_bytes,
+                        requirements.min_disk_gb.map(|gb| gb * BYTES_PER_GB),
+                        requirements.recommended_disk_gb.map(|gb| gb * BYTES_PER_GB),
+                        |bytes| format!("{} GB free", bytes / BYTES_PER_GB),
+                    );
+                    finding.message = format!("{} on {}", finding.message, disk.mount_point);
+                    finding
+                }
+                None => RequirementFinding {
+                    resource: Resource::Disk,
+                    status: CheckStatus::Warn,
+                    message: format!(
+                        "Could not find the disk backing the Docker data root {}",
+                        data_root.unwrap_or("(unknown)")
+                    ),
+                },
+            });
+        }
+
+        Self {
+            package: package.to_string(),
+            findings,
+        }
+    }
+
+    /// Returns the worst status of all findings.
+    #[must_use]
+    pub fn status(&self) -> CheckStatus {
+        self.findings
+            .iter()
+            .map(|finding| finding.status)
+            .max()
+            .unwrap_or(CheckStatus::Pass)
+    }
+}
+
+fn compare(
+    resource: Resource,
+    actual: u64,
+    min: Option<u64>,
+    recommended: Option<u64>,
+    describe: impl Fn(u64) -> String,
+) -> RequirementFinding {
+    let (status, message) = if let Some(min) = min.filter(|min| actual < *min) {
+        (
+            CheckStatus::Fail,
+            format!(
+                "Found {}, at least {} are required",
+                describe(actual),
+                describe(min)
+            ),
+        )
+    } else if let Some(recommended) = recommended.filter(|recommended| actual < *recommended) {
+        (
+            CheckStatus::Warn,
+            format!(
+                "Found {}, {} are recommended",
+                describe(actual),
+                describe(recommended)
+            ),
+        )
+    } else {
+        (CheckStatus::Pass, format!("Found {}", describe(actual)))
+    };
+
+    RequirementFinding {
+        resource,
+        status,
+        message,
+    }
+}
+
+fn find_disk<'a>(disks: &'a [DiskInfo], path: &str) -> Option<&'a DiskInfo> {
+    disks
+        .iter()
+        .filter(|disk| {
+            let mount_point = disk.mount_point.trim_end_matches('/');
+            path == mount_point
+                || path
+                    .strip_prefix(mount_point)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .max_by_key(|disk| disk.mount_point.len())
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            CheckStatus::Pass => "pass",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "fail",
+        };
+        write!(f, "{status}")
+    }
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let resource = match self {
+            Resource::Cores => "CPU",
+            Resource::Memory => "Memory",
+            Resource::Disk => "Disk",
+        };
+        write!(f, "{resource}")
+    }
+}
+
+impl fmt::Display for RequirementsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Requirements for {}: {}", self.package, self.status())?;
+        for finding in &self.findings {
+            writeln!(
+                f,
+                "- [{}] {}: {}",
+                finding.status, finding.resource, finding.message
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::system_info::{MemoryInfo, ProcessorInfo, StorageInfo};
+
+    fn disk(mount_point: &str, available_gb: u64) -> DiskInfo {
+        DiskInfo {
+            name: mount_point.to_string(),
+            mount_point: mount_point.to_string(),
+            total_bytes: 4000 * BYTES_PER_GB,
+            available_bytes: available_gb * BYTES_PER_GB,
+            total_display: String::new(),
+            available_display: String::new(),
+            disk_type: "ext4".to_string(),
+        }
+    }
+
+    fn system() -> SystemInfo {
+        SystemInfo {
+            processor: ProcessorInfo {
+                name: "CPU".to_string(),
+                cores: 8,
+                frequency_ghz: 3.0,
+                architecture: "x86_64".to_string(),
+            },
+            memory: MemoryInfo {
+                total_bytes: 16 * BYTES_PER_GB,
+                total_display: String::new(),
+            },
+            storage: StorageInfo {
+                disks: vec![disk("/", 200), disk("/var/lib/docker", 1000)],
+            },
+        }
+    }
+
+    #[test]
+    fn checks_requirements_against_the_host() {
+        let requirements = Requirements {
+            min_cores: Some(4),
+            recommended_cores: Some(8),
+            min_memory_gb: Some(8),
+            recommended_memory_gb: Some(32),
+            min_disk_gb: Some(2000),
+            recommended_disk_gb: None,
+        };
+
+        let report = RequirementsReport::check(
+            "Ethereum",
+            &requirements,
+            &system(),
+            Some("/var/lib/docker"),
+        );
+        let statuses: Vec<_> = report.findings.iter().map(|f| f.status).collect();
+        assert_eq!(
+            statuses,
+            [CheckStatus::Pass, CheckStatus::Warn, CheckStatus::Fail]
+        );
+        assert!(report.findings[2].message.ends_with("on /var/lib/docker"));
+        assert_eq!(report.status(), CheckStatus::Fail);
+
+        let report = RequirementsReport::check("Ethereum", &requirements, &system(), None);
+        assert_eq!(report.findings[2].status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn finds_the_disk_with_the_longest_mount_point() {
+        let disks = [disk("/", 1), disk("/var", 2), disk("/var/lib/docker", 3)];
+        assert_eq!(
+            find_disk(&disks, "/var/lib/docker").unwrap().mount_point,
+            "/var/lib/docker"
+        );
+        assert_eq!(find_disk(&disks, "/variable").unwrap().mount_point, "/");
+        assert_eq!(find_disk(&disks, "/var/lib").unwrap().mount_point, "/var");
+    }
+}
@@ -1,13 +1,14 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SystemInfo {
     pub processor: ProcessorInfo,
     pub memory: MemoryInfo,
     pub storage: StorageInfo,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProcessorInfo {
     pub name: String,
     pub cores: u32,
@@ -15,18 +16,18 @@ pub struct ProcessorInfo {
     pub architecture: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct MemoryInfo {
     pub total_bytes: u64,
     pub total_display: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct StorageInfo {
     pub disks: Vec<DiskInfo>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DiskInfo {
     pub name: String,
     pub mount_point: String,
@@ -0,0 +1,47 @@
+use ring::digest::{SHA256, digest};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// The certificate the web server serves HTTPS with.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TlsCertificate {
+    pub cert_path: String,
+    pub key_path: String,
+    /// The SHA-256 fingerprint clients pin, see [`fingerprint`].
+    pub fingerprint: String,
+    /// Whether Kittynode generated the certificate rather than the user supplying it.
+    pub self_signed: bool,
+}
+
+/// A user-supplied PEM certificate and key for the web server to serve.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TlsCertificatePaths {
+    pub cert_path: String,
+    pub key_path: String,
+}
+
+/// Returns the SHA-256 fingerprint of a DER-encoded certificate, as colon-separated
+/// uppercase hex like `openssl x509 -fingerprint -sha256` prints it.
+#[must_use]
+pub fn fingerprint(der: &[u8]) -> String {
+    digest(&SHA256, der)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+impl fmt::Display for TlsCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let origin = if self.self_signed {
+            "self-signed"
+        } else {
+            "user-supplied"
+        };
+        writeln!(f, "Certificate: {} ({})", self.cert_path, origin)?;
+        writeln!(f, "Key: {}", self.key_path)?;
+        write!(f, "SHA-256 fingerprint: {}", self.fingerprint)
+    }
+}
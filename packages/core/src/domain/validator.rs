@@ -0,0 +1,175 @@
+use eyre::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use utoipa::ToSchema;
+
+/// A validator key loaded in the validator client, as listed by the Keymanager API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ValidatorKey {
+    pub validating_pubkey: String,
+    pub derivation_path: Option<String>,
+    /// Whether the key is managed outside the Keymanager API and cannot be removed through it.
+    #[serde(default)]
+    pub readonly: bool,
+}
+
+/// An EIP-2335 keystore to import, with the password that decrypts it.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KeystoreImport {
+    /// The keystore JSON, as stored in a `keystore-*.json` file.
+    pub keystore: String,
+    pub password: String,
+}
+
+/// Keystores to import together, as sent to the Kittynode server.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KeystoreImportRequest {
+    pub keystores: Vec<KeystoreImport>,
+    /// The EIP-3076 slashing protection interchange of keys used in another validator client.
+    #[serde(default)]
+    pub slashing_protection: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Imported,
+    Duplicate,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KeystoreImportResult {
+    pub pubkey: String,
+    pub status: ImportStatus,
+    #[serde(default)]
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeleteStatus {
+    Deleted,
+    /// The key was not loaded, but slashing protection data for it was found.
+    NotActive,
+    NotFound,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KeystoreDeleteResult {
+    pub pubkey: String,
+    pub status: DeleteStatus,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// The outcome of removing validator keys.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RemovedValidators {
+    pub results: Vec<KeystoreDeleteResult>,
+    /// The EIP-3076 slashing protection interchange of the removed keys, needed to safely run
+    /// them in another validator client.
+    pub slashing_protection: String,
+    /// Where the slashing protection interchange was saved.
+    pub slashing_protection_path: String,
+}
+
+/// Returns the `0x` prefixed public key of an EIP-2335 keystore.
+pub fn keystore_pubkey(keystore: &str) -> Result<String> {
+    #[derive(Deserialize)]
+    struct Keystore {
+        pubkey: Option<String>,
+    }
+
+    let keystore: Keystore = serde_json::from_str(keystore)?;
+    let Some(pubkey) = keystore.pubkey else {
+        bail!("Keystore has no public key");
+    };
+    let pubkey = format!("0x{}", pubkey.trim_start_matches("0x"));
+    validate_pubkey(&pubkey)?;
+    Ok(pubkey)
+}
+
+/// Checks that a validator public key is `0x` followed by 96 hex characters.
+pub fn validate_pubkey(pubkey: &str) -> Result<()> {
+    if !is_hex(pubkey, 96) {
+        bail!("'{}' is not a validator public key", pubkey);
+    }
+    Ok(())
+}
+
+/// Checks that an execution address is `0x` followed by 40 hex characters.
+pub fn validate_address(address: &str) -> Result<()> {
+    if !is_hex(address, 40) {
+        bail!("'{}' is not an Ethereum address", address);
+    }
+    Ok(())
+}
+
+pub(crate) fn is_hex(value: &str, len: usize) -> bool {
+    value
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == len && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+impl fmt::Display for ValidatorKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.validating_pubkey)?;
+        if let Some(path) = &self.derivation_path {
+            write!(f, " ({path})")?;
+        }
+        if self.readonly {
+            write!(f, " [readonly]")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for KeystoreImportResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self.status {
+            ImportStatus::Imported => "imported",
+            ImportStatus::Duplicate => "duplicate",
+            ImportStatus::Error => "error",
+        };
+        write!(f, "{}: {}", self.pubkey, status)?;
+        if !self.message.is_empty() {
+            write!(f, " ({})", self.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for KeystoreDeleteResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self.status {
+            DeleteStatus::Deleted => "deleted",
+            DeleteStatus::NotActive => "not active",
+            DeleteStatus::NotFound => "not found",
+            DeleteStatus::Error => "error",
+        };
+        write!(f, "{}: {}", self.pubkey, status)?;
+        if !self.message.is_empty() {
+            write!(f, " ({})", self.message)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_keystore_pubkey() {
+        let pubkey = "a".repeat(96);
+        let keystore = format!(r#"{{"crypto": {{}}, "pubkey": "{pubkey}", "version": 4}}"#);
+        assert_eq!(keystore_pubkey(&keystore).unwrap(), format!("0x{pubkey}"));
+
+        assert!(keystore_pubkey(r#"{"pubkey": "0x1234"}"#).is_err());
+        assert!(keystore_pubkey(r#"{"version": 4}"#).is_err());
+        assert!(validate_address("0x0000000000000000000000000000000000000000").is_ok());
+        assert!(validate_address("0x00").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::domain::auth::AuthState;
+use crate::infra::file::{kittynode_path, write_private_file};
+use eyre::Result;
+use rand::Rng;
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+
+/// Letters and digits that cannot be mistaken for each other when typed.
+const PAIRING_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+pub struct AuthStore;
+
+impl AuthStore {
+    /// Loads the API tokens and pairing codes of the web server. The file is always replaced as a
+    /// whole, so this needs no lock.
+    pub fn load() -> Result<AuthState> {
+        read_state(&Self::auth_file_path()?)
+    }
+
+    /// Changes the API tokens and pairing codes, readable only by the current user.
+    ///
+    /// The file is locked from loading to saving, so concurrent requests and processes cannot
+    /// lose each other's changes. It is only written when `change` changed the state.
+    pub fn update<T>(change: impl FnOnce(&mut AuthState) -> T) -> Result<T> {
+        update_state(&Self::auth_file_path()?, change)
+    }
+
+    fn auth_file_path() -> Result<PathBuf> {
+        Ok(kittynode_path()?.join("auth.toml"))
+    }
+}
+
+fn read_state(auth_path: &Path) -> Result<AuthState> {
+    if !auth_path.exists() {
+        return Ok(AuthState::default());
+    }
+    let toml_str = fs::read_to_string(auth_path)?;
+    Ok(toml::from_str(&toml_str)?)
+}
+
+fn update_state<T>(auth_path: &Path, change: impl FnOnce(&mut AuthState) -> T) -> Result<T> {
+    // The auth file itself is replaced, so a separate file that never is holds the lock
+    if let Some(parent) = auth_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(auth_path.with_extension("lock"))?;
+    lock.lock()?;
+
+    let mut state = read_state(auth_path)?;
+    let before = toml::to_string(&state)?;
+    let result = change(&mut state);
+    let after = toml::to_string(&state)?;
+    if after != before {
+        write_private_file(auth_path, &after)?;
+    }
+    Ok(result)
+}
+
+/// Returns a pairing code such as `K7QM-2XFD`.
+pub(crate) fn generate_pairing_code() -> String {
+    let mut rng = rand::rng();
+    let mut code: String = (0..8)
+        .map(|_| {
+            char::from(PAIRING_CODE_ALPHABET[rng.random_range(0..PAIRING_CODE_ALPHABET.len())])
+        })
+        .collect();
+    code.insert(4, '-');
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::auth::{ApiToken, Scope};
+    use chrono::Utc;
+    use tempfile::tempdir;
+
+    fn token(name: &str) -> ApiToken {
+        ApiToken {
+            name: name.to_string(),
+            scope: Scope::Read,
+            token: format!("{name}-token"),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn concurrent_updates_keep_every_change() {
+        let temp_dir = tempdir().unwrap();
+        let auth_path = temp_dir.path().join("auth.toml");
+        update_state(&auth_path, |state| state.tokens.push(token("seed"))).unwrap();
+
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let auth_path = &auth_path;
+                scope.spawn(move || {
+                    update_state(auth_path, |state| {
+                        state.tokens.push(token(&format!("client-{i}")));
+                    })
+                    .unwrap();
+                });
+            }
+            // Readers do not take the lock, so they must never see a partially written file
+            for _ in 0..4 {
+                let auth_path = &auth_path;
+                scope.spawn(move || {
+                    for _ in 0..200 {
+                        let state = read_state(auth_path).unwrap();
+                        assert_eq!(state.authorize("seed-token"), Some(Scope::Read));
+                    }
+                });
+            }
+        });
+        assert_eq!(read_state(&auth_path).unwrap().tokens.len(), 9);
+    }
+}
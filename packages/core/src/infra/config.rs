@@ -1,7 +1,10 @@
 use crate::domain::config::Config;
-use crate::infra::file::kittynode_path;
+use crate::infra::file::{kittynode_path, write_private_file};
 use eyre::Result;
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 pub struct ConfigStore;
 
@@ -17,15 +20,10 @@ impl ConfigStore {
         Ok(config)
     }
 
-    /// Saves the configuration to a TOML file.
+    /// Saves the configuration to a TOML file, readable only by the current user as it holds the
+    /// API token of the paired server.
     pub fn save(config: &Config) -> Result<()> {
-        let config_path = Self::config_file_path()?;
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        let toml_str = toml::to_string(config)?;
-        fs::write(config_path, toml_str)?;
-        Ok(())
+        write_config(&Self::config_file_path()?, config)
     }
 
     /// Returns the path to the configuration file.
@@ -35,3 +33,33 @@ impl ConfigStore {
         Ok(path)
     }
 }
+
+fn write_config(config_path: &Path, config: &Config) -> Result<()> {
+    write_private_file(config_path, &toml::to_string(config)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn saves_the_config_privately() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join(".kittynode").join("config.toml");
+        let config = Config {
+            server_token: "secret".to_string(),
+            ..Config::default()
+        };
+
+        write_config(&config_path, &config).unwrap();
+        let saved: Config = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(saved.server_token, "secret");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&config_path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -1,184 +1,447 @@
-use crate::domain::container::{Binding, Container};
+use crate::domain::container::{Binding, Container, RestartPolicy};
+use crate::domain::logs::{LogLine, LogLineStream, LogSource, LogsQuery};
+use crate::domain::package_status::{ContainerState, ContainerStatus};
+use crate::domain::ports::PublishedPort;
+use crate::infra::pull_progress::{OnPullProgress, PullTracker};
+use crate::infra::runtime::ContainerRuntime;
 use bollard::{
     Docker,
-    container::{Config, CreateContainerOptions, ListContainersOptions, StartContainerOptions},
+    container::{
+        Config, CreateContainerOptions, ListContainersOptions, LogOutput, LogsOptions,
+        StartContainerOptions, StopContainerOptions,
+    },
     image::CreateImageOptions,
-    models::EndpointSettings,
-    network::{ConnectNetworkOptions, CreateNetworkOptions},
-    secret::{ContainerSummary, HostConfig},
+    models::{
+        EndpointSettings, HostConfigLogConfig, ResourcesUlimits,
+        RestartPolicy as DockerRestartPolicy, RestartPolicyNameEnum,
+    },
+    network::{ConnectNetworkOptions, CreateNetworkOptions, DisconnectNetworkOptions},
+    secret::{ContainerStateStatusEnum, ContainerSummary, HostConfig},
 };
-use eyre::{Report, Result};
+use chrono::{DateTime, Utc};
+use eyre::{Context, Report, Result, bail};
 use std::collections::HashMap;
+use std::time::Duration;
 use tokio_stream::StreamExt;
-use tracing::{error, info};
+
+/// How much longer than the stop timeout to wait for Docker to answer a stop request.
+const STOP_REQUEST_MARGIN: Duration = Duration::from_secs(30);
+/// How long to wait for a container stopped with its own stop timeout, which is not known here.
+const MAX_STOP_REQUEST_TIMEOUT: Duration = Duration::from_secs(15 * 60);
 
 pub(crate) fn get_docker_instance() -> Result<Docker> {
     Docker::connect_with_local_defaults().map_err(Report::from)
 }
 
-pub(crate) async fn create_or_recreate_network(docker: &Docker, network_name: &str) -> Result<()> {
-    // Check if network already exists
-    let network_exists = docker
-        .list_networks::<String>(None)
-        .await?
-        .iter()
-        .any(|n| n.name.as_deref() == Some(network_name));
-
-    // Remove network if it already exists
-    if network_exists {
-        docker.remove_network(network_name).await?;
-        info!("Removed existing network: '{}'", network_name);
-    }
-
-    // Create new network
-    docker
-        .create_network(CreateNetworkOptions {
-            name: network_name,
-            check_duplicate: true,
-            driver: "bridge",
-            ..Default::default()
+/// A container runtime backed by the local Docker engine.
+pub(crate) struct DockerRuntime {
+    docker: Docker,
+}
+
+impl DockerRuntime {
+    /// Connects to the local Docker engine.
+    pub(crate) fn connect() -> Result<Self> {
+        Ok(Self {
+            docker: get_docker_instance()?,
         })
-        .await?;
-    info!("Created new network: '{}'", network_name);
+    }
 
-    Ok(())
-}
+    /// Stops a container, keeping the request open for as long as the container may take to
+    /// exit rather than the default client timeout.
+    async fn stop_gracefully(
+        &self,
+        name: &str,
+        timeout_secs: Option<i64>,
+    ) -> Result<(), bollard::errors::Error> {
+        let request_timeout = match timeout_secs {
+            Some(secs) => {
+                Duration::from_secs(u64::try_from(secs).unwrap_or(0)) + STOP_REQUEST_MARGIN
+            }
+            None => MAX_STOP_REQUEST_TIMEOUT,
+        };
+        self.docker
+            .clone()
+            .with_timeout(request_timeout)
+            .stop_container(name, timeout_secs.map(|t| StopContainerOptions { t }))
+            .await
+    }
 
-pub(crate) async fn find_container(docker: &Docker, name: &str) -> Result<Vec<ContainerSummary>> {
-    let filters = HashMap::from([("name".to_string(), vec![name.to_string()])]);
+    async fn find_container(&self, name: &str) -> Result<Vec<ContainerSummary>> {
+        let filters = HashMap::from([("name".to_string(), vec![name.to_string()])]);
 
-    Ok(docker
-        .list_containers(Some(ListContainersOptions {
-            all: true,
-            filters,
-            ..Default::default()
-        }))
-        .await?)
+        Ok(self
+            .docker
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                filters,
+                ..Default::default()
+            }))
+            .await?)
+    }
 }
 
-pub(crate) async fn remove_container(docker: &Docker, name: &str) -> Result<()> {
-    for container in find_container(docker, name).await? {
-        let id = container
-            .id
-            .ok_or_else(|| eyre::eyre!("Container ID was None"))?;
-        docker.stop_container(&id, None).await.ok(); // Ignore stop errors
-        docker.remove_container(&id, None).await?;
+impl ContainerRuntime for DockerRuntime {
+    async fn data_root(&self) -> Result<String> {
+        self.docker
+            .info()
+            .await?
+            .docker_root_dir
+            .ok_or_else(|| eyre::eyre!("Docker did not report its root directory"))
     }
 
-    Ok(())
-}
+    async fn network_exists(&self, name: &str) -> Result<bool> {
+        Ok(self
+            .docker
+            .list_networks::<String>(None)
+            .await?
+            .iter()
+            .any(|n| n.name.as_deref() == Some(name)))
+    }
 
-pub(crate) async fn pull_and_start_container(
-    docker: &Docker,
-    container: &Container,
-    network_name: &str,
-) -> Result<()> {
-    let options = Some(CreateImageOptions {
-        from_image: container.image.to_string(),
-        tag: "latest".to_string(),
-        ..Default::default()
-    });
-
-    let mut stream = docker.create_image(options, None, None);
-    while let Some(item) = stream.next().await {
-        match item {
-            Ok(info) => info!("Pulling image info: {:?}", info),
-            Err(e) => error!("Error pulling image: {:?}", e),
+    async fn create_network(&self, name: &str) -> Result<()> {
+        self.docker
+            .create_network(CreateNetworkOptions {
+                name,
+                check_duplicate: true,
+                driver: "bridge",
+                ..Default::default()
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_network(&self, name: &str) -> Result<()> {
+        self.docker.remove_network(name).await?;
+        Ok(())
+    }
+
+    async fn pull_image(&self, reference: &str, on_progress: OnPullProgress<'_>) -> Result<()> {
+        let options = Some(CreateImageOptions {
+            from_image: reference.to_string(),
+            ..Default::default()
+        });
+
+        let mut tracker = PullTracker::new(reference);
+        let mut stream = self.docker.create_image(options, None, None);
+        while let Some(item) = stream.next().await {
+            let info = item.wrap_err_with(|| format!("Failed to pull image '{reference}'"))?;
+            if let Some(error) = info.error {
+                bail!("Failed to pull image '{}': {}", reference, error);
+            }
+            if let (Some(layer), Some(status)) = (&info.id, &info.status) {
+                let detail = info.progress_detail.unwrap_or_default();
+                tracker.update(
+                    layer,
+                    status,
+                    detail.current.and_then(|n| u64::try_from(n).ok()),
+                    detail.total.and_then(|n| u64::try_from(n).ok()),
+                );
+                on_progress(&tracker.progress(false));
+            }
         }
+
+        on_progress(&tracker.progress(true));
+        Ok(())
     }
 
-    let port_bindings = container
-        .port_bindings
-        .iter()
-        .map(|(k, v)| (k.to_string(), Some(v.clone())))
-        .collect();
-
-    let bindings = container
-        .volume_bindings
-        .iter()
-        .chain(&container.file_bindings)
-        .map(create_binding_string)
-        .collect();
-
-    let host_config = HostConfig {
-        binds: Some(bindings),
-        port_bindings: Some(port_bindings),
-        ..Default::default()
-    };
-
-    let config = Config {
-        image: Some(container.image.to_string()),
-        cmd: Some(container.cmd.clone()),
-        host_config: Some(host_config),
-        ..Default::default()
-    };
-
-    let created_container = docker
-        .create_container(
-            Some(CreateContainerOptions {
-                name: container.name.to_string(),
-                ..Default::default()
+    async fn image_digest(&self, reference: &str) -> Result<Option<String>> {
+        let image = self.docker.inspect_image(reference).await?;
+        let repository = reference
+            .split_once('@')
+            .map_or(reference, |(repository, _)| repository);
+        let repository = repository
+            .rsplit_once(':')
+            .filter(|(_, tag)| !tag.contains('/'))
+            .map_or(repository, |(repository, _)| repository);
+
+        Ok(image
+            .repo_digests
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|repo_digest| repo_digest.split_once('@'))
+            .find(|(name, _)| *name == repository)
+            .map(|(_, digest)| digest.to_string()))
+    }
+
+    async fn remove_image(&self, reference: &str) -> Result<()> {
+        self.docker.remove_image(reference, None, None).await?;
+        Ok(())
+    }
+
+    async fn container_exists(&self, name: &str) -> Result<bool> {
+        Ok(!self.find_container(name).await?.is_empty())
+    }
+
+    async fn create_container(&self, container: &Container) -> Result<()> {
+        let port_bindings = container
+            .port_bindings
+            .iter()
+            .map(|(k, v)| (k.to_string(), Some(v.clone())))
+            .collect();
+
+        let bindings = container
+            .volume_bindings
+            .iter()
+            .chain(&container.file_bindings)
+            .map(create_binding_string)
+            .collect();
+
+        let restart_policy = DockerRestartPolicy {
+            name: Some(match container.restart_policy {
+                RestartPolicy::No => RestartPolicyNameEnum::NO,
+                RestartPolicy::Always => RestartPolicyNameEnum::ALWAYS,
+                RestartPolicy::UnlessStopped => RestartPolicyNameEnum::UNLESS_STOPPED,
+                RestartPolicy::OnFailure => RestartPolicyNameEnum::ON_FAILURE,
             }),
-            config,
-        )
-        .await?;
-    info!("Container {} created successfully.", container.name);
-
-    docker
-        .start_container(&created_container.id, None::<StartContainerOptions<String>>)
-        .await?;
-    info!("Container {} started successfully.", container.name);
-
-    docker
-        .connect_network(
-            network_name,
-            ConnectNetworkOptions {
-                container: container.name.to_string(),
-                endpoint_config: EndpointSettings::default(),
+            maximum_retry_count: None,
+        };
+
+        let ulimits = container
+            .ulimits
+            .iter()
+            .map(|ulimit| ResourcesUlimits {
+                name: Some(ulimit.name.clone()),
+                soft: Some(ulimit.soft),
+                hard: Some(ulimit.hard),
+            })
+            .collect();
+
+        let log_config = container
+            .log_config
+            .as_ref()
+            .map(|log| HostConfigLogConfig {
+                typ: Some(log.driver.clone()),
+                config: Some(log.options.clone()),
+            });
+
+        let host_config = HostConfig {
+            binds: Some(bindings),
+            port_bindings: Some(port_bindings),
+            restart_policy: Some(restart_policy),
+            memory: container.limits.memory_bytes,
+            nano_cpus: container.limits.nano_cpus,
+            ulimits: Some(ulimits),
+            log_config,
+            ..Default::default()
+        };
+
+        let config = Config {
+            image: Some(container.image_reference()),
+            cmd: Some(container.cmd.clone()),
+            host_config: Some(host_config),
+            stop_signal: container.stop_signal.clone(),
+            stop_timeout: container.stop_timeout_secs,
+            ..Default::default()
+        };
+
+        self.docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name: container.name.to_string(),
+                    ..Default::default()
+                }),
+                config,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn start_container(&self, name: &str) -> Result<()> {
+        self.docker
+            .start_container(name, None::<StartContainerOptions<String>>)
+            .await?;
+        Ok(())
+    }
+
+    async fn stop_container(&self, name: &str, timeout_secs: Option<i64>) -> Result<()> {
+        self.stop_gracefully(name, timeout_secs)
+            .await
+            .wrap_err_with(|| format!("Failed to stop container '{name}'"))
+    }
+
+    async fn container_status(&self, name: &str) -> Result<ContainerStatus> {
+        let container = match self.docker.inspect_container(name, None).await {
+            Ok(container) => container,
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => return Ok(ContainerStatus::missing(name)),
+            Err(e) => return Err(e.into()),
+        };
+
+        let state = container.state.unwrap_or_default();
+        let status = match state.status {
+            Some(ContainerStateStatusEnum::RUNNING) => ContainerState::Running,
+            Some(ContainerStateStatusEnum::PAUSED) => ContainerState::Paused,
+            Some(ContainerStateStatusEnum::RESTARTING) => ContainerState::Restarting,
+            Some(ContainerStateStatusEnum::REMOVING) => ContainerState::Removing,
+            Some(ContainerStateStatusEnum::EXITED) => ContainerState::Exited,
+            Some(ContainerStateStatusEnum::DEAD) => ContainerState::Dead,
+            _ => ContainerState::Created,
+        };
+
+        let uptime_secs = match status {
+            ContainerState::Running => state
+                .started_at
+                .as_deref()
+                .and_then(|started_at| DateTime::parse_from_rfc3339(started_at).ok())
+                .and_then(|started_at| {
+                    u64::try_from((Utc::now() - started_at.with_timezone(&Utc)).num_seconds()).ok()
+                }),
+            _ => None,
+        };
+
+        let image_digest = match &container.image {
+            Some(image_id) => self
+                .docker
+                .inspect_image(image_id)
+                .await
+                .ok()
+                .and_then(|image| image.repo_digests)
+                .and_then(|digests| digests.into_iter().next())
+                .and_then(|digest| digest.split_once('@').map(|(_, d)| d.to_string())),
+            None => None,
+        };
+
+        Ok(ContainerStatus {
+            name: name.to_string(),
+            state: status,
+            health: state
+                .health
+                .and_then(|health| health.status)
+                .map(|status| status.to_string())
+                .filter(|status| !status.is_empty() && status != "none"),
+            exit_code: match status {
+                ContainerState::Exited | ContainerState::Dead => state.exit_code,
+                _ => None,
             },
-        )
-        .await?;
-    info!(
-        "Container {} connected to network '{}'.",
-        container.name, network_name
-    );
-
-    Ok(())
-}
+            uptime_secs,
+            restart_count: container
+                .restart_count
+                .and_then(|count| u64::try_from(count).ok())
+                .unwrap_or(0),
+            image_digest,
+        })
+    }
 
-pub async fn get_container_logs(
-    docker: &Docker,
-    container_name: &str,
-    tail_lines: Option<usize>,
-) -> Result<Vec<String>> {
-    let tail = tail_lines.map_or_else(|| "all".to_string(), |n| n.to_string());
-
-    let options = bollard::container::LogsOptions::<String> {
-        stdout: true,
-        stderr: true,
-        follow: false,
-        timestamps: true,
-        tail,
-        ..Default::default()
-    };
-
-    let mut stream = docker.logs(container_name, Some(options));
-    let mut log_strings = Vec::new();
-
-    while let Some(result) = stream.next().await {
-        match result {
-            Ok(output) => match output {
-                bollard::container::LogOutput::StdOut { message }
-                | bollard::container::LogOutput::StdErr { message } => {
-                    log_strings.push(String::from_utf8_lossy(&message).to_string());
+    async fn remove_container(&self, name: &str, timeout_secs: Option<i64>) -> Result<()> {
+        for container in self.find_container(name).await? {
+            let id = container
+                .id
+                .ok_or_else(|| eyre::eyre!("Container ID was None"))?;
+            match self.stop_gracefully(&id, timeout_secs).await {
+                // The container was removed in the meantime
+                Err(bollard::errors::Error::DockerResponseServerError {
+                    status_code: 404, ..
+                }) => continue,
+                Err(e) => {
+                    return Err(e).wrap_err_with(|| format!("Failed to stop container '{name}'"));
                 }
-                _ => {}
-            },
-            Err(e) => return Err(e.into()),
+                Ok(()) => {}
+            }
+            self.docker.remove_container(&id, None).await?;
         }
+
+        Ok(())
     }
 
-    Ok(log_strings)
+    async fn connect_network(&self, network: &str, container: &str) -> Result<()> {
+        self.docker
+            .connect_network(
+                network,
+                ConnectNetworkOptions {
+                    container: container.to_string(),
+                    endpoint_config: EndpointSettings::default(),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn disconnect_network(&self, network: &str, container: &str) -> Result<()> {
+        self.docker
+            .disconnect_network(
+                network,
+                DisconnectNetworkOptions {
+                    container: container.to_string(),
+                    force: false,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn container_networks(&self, name: &str) -> Result<Vec<String>> {
+        let container = self.docker.inspect_container(name, None).await?;
+        Ok(container
+            .network_settings
+            .and_then(|settings| settings.networks)
+            .map(|networks| networks.into_keys().collect())
+            .unwrap_or_default())
+    }
+
+    async fn published_ports(&self) -> Result<Vec<PublishedPort>> {
+        let containers = self
+            .docker
+            .list_containers(None::<ListContainersOptions<String>>)
+            .await?;
+
+        let mut ports = Vec::new();
+        for container in containers {
+            let name = container
+                .names
+                .and_then(|names| names.into_iter().next())
+                .map(|name| name.trim_start_matches('/').to_string())
+                .unwrap_or_default();
+            for port in container.ports.unwrap_or_default() {
+                if let Some(host_port) = port.public_port {
+                    ports.push(PublishedPort {
+                        container: name.clone(),
+                        host_ip: port.ip.unwrap_or_default(),
+                        host_port,
+                        protocol: port.typ.map(|typ| typ.to_string()).unwrap_or_default(),
+                    });
+                }
+            }
+        }
+        Ok(ports)
+    }
+
+    async fn remove_volume(&self, name: &str) -> Result<()> {
+        self.docker.remove_volume(name, None).await?;
+        Ok(())
+    }
+
+    fn container_logs(&self, name: &str, query: &LogsQuery) -> LogLineStream {
+        let options = LogsOptions::<String> {
+            follow: query.follow,
+            stdout: query.stdout,
+            stderr: query.stderr,
+            since: query.since.map_or(0, |since| since.timestamp()),
+            until: query.until.map_or(0, |until| until.timestamp()),
+            timestamps: true,
+            tail: query
+                .tail
+                .map_or_else(|| "all".to_string(), |n| n.to_string()),
+        };
+
+        let stream = self
+            .docker
+            .logs(name, Some(options))
+            .filter_map(|output| match output {
+                Ok(LogOutput::StdOut { message }) => Some(Ok(LogLine::parse(
+                    LogSource::Stdout,
+                    &String::from_utf8_lossy(&message),
+                ))),
+                Ok(LogOutput::StdErr { message }) => Some(Ok(LogLine::parse(
+                    LogSource::Stderr,
+                    &String::from_utf8_lossy(&message),
+                ))),
+                Ok(_) => None,
+                Err(e) => Some(Err(e.into())),
+            });
+        Box::pin(stream)
+    }
 }
 
 fn create_binding_string(binding: &Binding) -> String {
@@ -0,0 +1,341 @@
+use crate::domain::container::Container;
+use crate::domain::logs::{LogLine, LogLineStream, LogSource, LogsQuery};
+use crate::domain::package_status::{ContainerState, ContainerStatus};
+use crate::domain::ports::PublishedPort;
+use crate::infra::pull_progress::{OnPullProgress, PullTracker};
+use crate::infra::runtime::ContainerRuntime;
+use eyre::{Result, bail};
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Mutex, MutexGuard};
+
+/// An in-memory container runtime for unit tests.
+#[derive(Default)]
+pub(crate) struct FakeRuntime {
+    state: Mutex<FakeState>,
+}
+
+#[derive(Default)]
+pub(crate) struct FakeState {
+    pub(crate) networks: HashSet<String>,
+    pub(crate) images: HashSet<String>,
+    pub(crate) containers: HashMap<String, FakeContainer>,
+    pub(crate) volumes: HashSet<String>,
+    pub(crate) logs: HashMap<String, Vec<LogLine>>,
+    /// Image references whose containers exit immediately after starting.
+    pub(crate) crashing_images: HashSet<String>,
+    /// Image references whose containers crash and are restarted by their restart policy, so
+    /// they are running whenever they are sampled.
+    pub(crate) restarting_images: HashSet<String>,
+    /// Host ports held by processes outside the runtime.
+    pub(crate) host_listeners: HashSet<u16>,
+    /// The stop timeout of the last stop of each container.
+    pub(crate) stop_timeouts: HashMap<String, Option<i64>>,
+    /// Operations that should fail, as `(operation, target)` pairs.
+    pub(crate) failures: HashSet<(String, String)>,
+}
+
+#[derive(Clone)]
+pub(crate) struct FakeContainer {
+    pub(crate) container: Container,
+    pub(crate) running: bool,
+    pub(crate) networks: HashSet<String>,
+}
+
+impl FakeRuntime {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes every later call of `operation` on `target` fail.
+    pub(crate) fn fail_on(&self, operation: &str, target: &str) {
+        self.state()
+            .failures
+            .insert((operation.to_string(), target.to_string()));
+    }
+
+    pub(crate) fn state(&self) -> MutexGuard<'_, FakeState> {
+        self.state.lock().unwrap()
+    }
+
+    fn check(&self, operation: &str, target: &str) -> Result<()> {
+        if self
+            .state()
+            .failures
+            .contains(&(operation.to_string(), target.to_string()))
+        {
+            bail!("Injected failure: {} '{}'", operation, target);
+        }
+        Ok(())
+    }
+}
+
+/// Returns a stable digest for an image reference.
+fn fake_digest(reference: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    reference.hash(&mut hasher);
+    format!("sha256:{:064x}", hasher.finish())
+}
+
+impl ContainerRuntime for FakeRuntime {
+    async fn data_root(&self) -> Result<String> {
+        self.check("data_root", "")?;
+        Ok("/var/lib/docker".to_string())
+    }
+
+    async fn network_exists(&self, name: &str) -> Result<bool> {
+        self.check("network_exists", name)?;
+        Ok(self.state().networks.contains(name))
+    }
+
+    async fn create_network(&self, name: &str) -> Result<()> {
+        self.check("create_network", name)?;
+        if !self.state().networks.insert(name.to_string()) {
+            bail!("Network '{}' already exists", name);
+        }
+        Ok(())
+    }
+
+    async fn remove_network(&self, name: &str) -> Result<()> {
+        self.check("remove_network", name)?;
+        let mut state = self.state();
+        if state
+            .containers
+            .values()
+            .any(|fake| fake.networks.contains(name))
+        {
+            bail!("Network '{}' has active endpoints", name);
+        }
+        if !state.networks.remove(name) {
+            bail!("Network '{}' not found", name);
+        }
+        Ok(())
+    }
+
+    async fn pull_image(&self, reference: &str, on_progress: OnPullProgress<'_>) -> Result<()> {
+        self.check("pull_image", reference)?;
+        self.state().images.insert(reference.to_string());
+        let mut tracker = PullTracker::new(reference);
+        tracker.update("layer", "Pull complete", None, None);
+        on_progress(&tracker.progress(true));
+        Ok(())
+    }
+
+    async fn image_digest(&self, reference: &str) -> Result<Option<String>> {
+        self.check("image_digest", reference)?;
+        if !self.state().images.contains(reference) {
+            bail!("Image '{}' has not been pulled", reference);
+        }
+        Ok(Some(fake_digest(reference)))
+    }
+
+    async fn remove_image(&self, reference: &str) -> Result<()> {
+        self.check("remove_image", reference)?;
+        if !self.state().images.remove(reference) {
+            bail!("Image '{}' not found", reference);
+        }
+        Ok(())
+    }
+
+    async fn container_exists(&self, name: &str) -> Result<bool> {
+        self.check("container_exists", name)?;
+        Ok(self.state().containers.contains_key(name))
+    }
+
+    async fn create_container(&self, container: &Container) -> Result<()> {
+        self.check("create_container", &container.name)?;
+        let mut state = self.state();
+        if !state.images.contains(&container.image_reference()) {
+            bail!(
+                "Image '{}' has not been pulled",
+                container.image_reference()
+            );
+        }
+        if state.containers.contains_key(&container.name) {
+            bail!("Container '{}' already exists", container.name);
+        }
+        for binding in &container.volume_bindings {
+            state.volumes.insert(binding.source.clone());
+        }
+        state.containers.insert(
+            container.name.clone(),
+            FakeContainer {
+                container: container.clone(),
+                running: false,
+                networks: HashSet::new(),
+            },
+        );
+        Ok(())
+    }
+
+    async fn start_container(&self, name: &str) -> Result<()> {
+        self.check("start_container", name)?;
+        let mut state = self.state();
+        let crashing = match state.containers.get(name) {
+            Some(fake) => state
+                .crashing_images
+                .contains(&fake.container.image_reference()),
+            None => bail!("Container '{}' not found", name),
+        };
+        if let Some(fake) = state.containers.get_mut(name) {
+            fake.running = !crashing;
+        }
+        Ok(())
+    }
+
+    async fn stop_container(&self, name: &str, timeout_secs: Option<i64>) -> Result<()> {
+        self.check("stop_container", name)?;
+        let mut state = self.state();
+        match state.containers.get_mut(name) {
+            Some(fake) => fake.running = false,
+            None => bail!("Container '{}' not found", name),
+        }
+        state.stop_timeouts.insert(name.to_string(), timeout_secs);
+        Ok(())
+    }
+
+    async fn container_status(&self, name: &str) -> Result<ContainerStatus> {
+        self.check("container_status", name)?;
+        let state = self.state();
+        let Some(fake) = state.containers.get(name) else {
+            return Ok(ContainerStatus::missing(name));
+        };
+        let reference = fake.container.image_reference();
+        let crashing = state.crashing_images.contains(&reference);
+        let restarts = if state.restarting_images.contains(&reference) {
+            3
+        } else {
+            0
+        };
+
+        Ok(ContainerStatus {
+            name: name.to_string(),
+            state: if fake.running {
+                ContainerState::Running
+            } else {
+                ContainerState::Exited
+            },
+            health: None,
+            exit_code: (!fake.running).then_some(i64::from(crashing)),
+            uptime_secs: fake.running.then_some(0),
+            restart_count: restarts,
+            image_digest: Some(fake_digest(&reference)),
+        })
+    }
+
+    async fn remove_container(&self, name: &str, timeout_secs: Option<i64>) -> Result<()> {
+        self.check("remove_container", name)?;
+        if self.state().containers.contains_key(name) {
+            self.stop_container(name, timeout_secs).await?;
+        }
+        self.state().containers.remove(name);
+        Ok(())
+    }
+
+    async fn connect_network(&self, network: &str, container: &str) -> Result<()> {
+        self.check("connect_network", container)?;
+        let mut state = self.state();
+        if !state.networks.contains(network) {
+            bail!("Network '{}' not found", network);
+        }
+        match state.containers.get_mut(container) {
+            Some(fake) => {
+                fake.networks.insert(network.to_string());
+            }
+            None => bail!("Container '{}' not found", container),
+        }
+        Ok(())
+    }
+
+    async fn disconnect_network(&self, network: &str, container: &str) -> Result<()> {
+        self.check("disconnect_network", container)?;
+        let mut state = self.state();
+        let Some(fake) = state.containers.get_mut(container) else {
+            bail!("Container '{}' not found", container);
+        };
+        if !fake.networks.remove(network) {
+            bail!(
+                "Container '{}' is not connected to '{}'",
+                container,
+                network
+            );
+        }
+        Ok(())
+    }
+
+    async fn container_networks(&self, name: &str) -> Result<Vec<String>> {
+        self.check("container_networks", name)?;
+        match self.state().containers.get(name) {
+            Some(fake) => Ok(fake.networks.iter().cloned().collect()),
+            None => bail!("Container '{}' not found", name),
+        }
+    }
+
+    async fn published_ports(&self) -> Result<Vec<PublishedPort>> {
+        self.check("published_ports", "")?;
+        let mut ports = Vec::new();
+        for fake in self.state().containers.values().filter(|fake| fake.running) {
+            for (container_port, bindings) in &fake.container.port_bindings {
+                let protocol = container_port.split_once('/').map_or("tcp", |(_, p)| p);
+                for binding in bindings {
+                    ports.push(PublishedPort {
+                        container: fake.container.name.clone(),
+                        host_ip: binding.host_ip.clone().unwrap_or_default(),
+                        host_port: binding
+                            .host_port
+                            .as_deref()
+                            .and_then(|port| port.parse().ok())
+                            .unwrap_or_default(),
+                        protocol: protocol.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(ports)
+    }
+
+    fn is_host_port_free(&self, port: &PublishedPort) -> bool {
+        !self.state().host_listeners.contains(&port.host_port)
+    }
+
+    async fn remove_volume(&self, name: &str) -> Result<()> {
+        self.check("remove_volume", name)?;
+        if !self.state().volumes.remove(name) {
+            bail!("Volume '{}' not found", name);
+        }
+        Ok(())
+    }
+
+    fn container_logs(&self, name: &str, query: &LogsQuery) -> LogLineStream {
+        let lines = self.check("container_logs", name).and_then(|()| {
+            let state = self.state();
+            if !state.containers.contains_key(name) {
+                bail!("Container '{}' not found", name);
+            }
+            let logs: Vec<LogLine> = state
+                .logs
+                .get(name)
+                .into_iter()
+                .flatten()
+                .filter(|line| match line.source {
+                    LogSource::Stdout => query.stdout,
+                    LogSource::Stderr => query.stderr,
+                })
+                .filter(|line| {
+                    line.timestamp.is_none_or(|timestamp| {
+                        query.since.is_none_or(|since| timestamp >= since)
+                            && query.until.is_none_or(|until| timestamp < until)
+                    })
+                })
+                .cloned()
+                .collect();
+            let skip = query.tail.map_or(0, |n| logs.len().saturating_sub(n));
+            Ok(logs.into_iter().skip(skip).map(Ok).collect::<Vec<_>>())
+        });
+
+        match lines {
+            Ok(lines) => Box::pin(tokio_stream::iter(lines)),
+            Err(e) => Box::pin(tokio_stream::iter([Err(e)])),
+        }
+    }
+}
@@ -1,9 +1,30 @@
 use eyre::{Context, Result};
 use rand::RngCore;
-use std::{fs, path::PathBuf};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
 use tracing::info;
 
+#[cfg(test)]
+thread_local! {
+    /// Replaces the `.kittynode` path in tests on the current thread.
+    static TEST_KITTYNODE_PATH: std::cell::RefCell<Option<PathBuf>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Makes `kittynode_path` return `path` for the rest of the test on the current thread.
+#[cfg(test)]
+pub(crate) fn set_test_kittynode_path(path: &Path) {
+    TEST_KITTYNODE_PATH.with_borrow_mut(|test_path| *test_path = Some(path.to_path_buf()));
+}
+
 pub(crate) fn kittynode_path() -> Result<PathBuf> {
+    #[cfg(test)]
+    if let Some(path) = TEST_KITTYNODE_PATH.with_borrow(Clone::clone) {
+        return Ok(path);
+    }
     home::home_dir()
         .map(|home| home.join(".kittynode"))
         .ok_or_else(|| eyre::eyre!("Failed to determine the .kittynode path"))
@@ -35,9 +56,63 @@ pub(crate) fn generate_jwt_secret_with_path(path: &PathBuf) -> Result<String> {
     Ok(secret)
 }
 
-pub(crate) fn generate_jwt_secret() -> Result<String> {
-    let path = kittynode_path()?;
-    generate_jwt_secret_with_path(&path)
+/// Returns 32 random bytes as hex, after the given prefix.
+pub(crate) fn generate_secret(prefix: &str) -> String {
+    let mut buf = [0u8; 32];
+    rand::rng().fill_bytes(&mut buf);
+    format!("{}{}", prefix, hex::encode(buf))
+}
+
+/// Writes a file only the current user can read, creating its parent directories. An existing
+/// file is replaced as a whole, so readers never see it partially written.
+pub(crate) fn write_private_file(path: &Path, contents: &str) -> Result<()> {
+    replace_file(path, contents, true)
+}
+
+/// Writes a file, creating its parent directories. An existing file is replaced as a whole, so
+/// readers never see it partially written.
+pub(crate) fn write_file_atomically(path: &Path, contents: &str) -> Result<()> {
+    replace_file(path, contents, false)
+}
+
+/// Writes a new file only the current user can read, failing with `ErrorKind::AlreadyExists`
+/// if the file exists.
+pub(crate) fn create_private_file(path: &Path, contents: &str) -> Result<()> {
+    create_file(path, contents, true)
+}
+
+/// Writes the contents to a new file next to `path` and renames it over `path`.
+fn replace_file(path: &Path, contents: &str, private: bool) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| eyre::eyre!("Invalid file path {}", path.display()))?
+        .to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{}.{}.tmp", file_name, generate_secret("")));
+    create_file(&temp_path, contents, private)?;
+    fs::rename(&temp_path, path).or_else(|e| {
+        let _ = fs::remove_file(&temp_path);
+        Err(e).wrap_err_with(|| format!("Failed to replace {}", path.display()))
+    })
+}
+
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn create_file(path: &Path, contents: &str, private: bool) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).wrap_err("Failed to create directory")?;
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    }
+    let mut file = options
+        .open(path)
+        .wrap_err_with(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -62,4 +137,23 @@ mod tests {
 
         assert_eq!(result.unwrap(), secret, "Secrets do not match");
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_private_file_makes_an_existing_file_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("password.txt");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private_file(&path, "new").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        assert!(create_private_file(&path, "newer").is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    }
 }
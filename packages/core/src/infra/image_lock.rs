@@ -0,0 +1,43 @@
+use crate::domain::image::ImageLock;
+use crate::infra::file::kittynode_path;
+use eyre::Result;
+use std::{fs, io::ErrorKind, path::PathBuf};
+
+pub struct ImageLockStore;
+
+impl ImageLockStore {
+    /// Loads the images recorded when the package was installed.
+    pub fn load(package_name: &str) -> Result<ImageLock> {
+        let lock_path = Self::lock_file_path(package_name)?;
+        if !lock_path.exists() {
+            return Ok(ImageLock::default());
+        }
+        let toml_str = fs::read_to_string(lock_path)?;
+        let lock = toml::from_str(&toml_str)?;
+        Ok(lock)
+    }
+
+    pub fn save(package_name: &str, lock: &ImageLock) -> Result<()> {
+        let lock_path = Self::lock_file_path(package_name)?;
+        if let Some(parent) = lock_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let toml_str = toml::to_string_pretty(lock)?;
+        fs::write(lock_path, toml_str)?;
+        Ok(())
+    }
+
+    pub fn remove(package_name: &str) -> Result<()> {
+        match fs::remove_file(Self::lock_file_path(package_name)?) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn lock_file_path(package_name: &str) -> Result<PathBuf> {
+        Ok(kittynode_path()?
+            .join("packages")
+            .join(package_name)
+            .join("images.toml"))
+    }
+}
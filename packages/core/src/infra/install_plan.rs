@@ -0,0 +1,280 @@
+use crate::domain::container::Container;
+use crate::domain::image::ImageLock;
+use crate::domain::package::Package;
+use crate::infra::file::{generate_jwt_secret_with_path, generate_secret, write_private_file};
+use crate::infra::package::pull_image;
+use crate::infra::pull_progress::OnPullProgress;
+use crate::infra::runtime::ContainerRuntime;
+use eyre::{Result, eyre};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{error, info, warn};
+
+/// A single step of a package install.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum InstallStep {
+    /// Writes the JWT secret shared by the execution and consensus clients.
+    GenerateJwtSecret(PathBuf),
+    /// Writes a random token the package reads, such as an API token.
+    GenerateSecret {
+        path: PathBuf,
+        prefix: String,
+    },
+    /// Creates a missing bind mount source under the Kittynode directory, so the engine does not
+    /// create it as root.
+    CreateDirectory(PathBuf),
+    CreateNetwork(String),
+    PullImage(String),
+    CreateContainer(String),
+    StartContainer(String),
+    ConnectNetwork(String),
+}
+
+/// Something an install step created, which is removed to undo the step.
+#[derive(Debug)]
+enum Created {
+    File(PathBuf),
+    Directory(PathBuf),
+    Network(String),
+    Container(String),
+}
+
+/// The ordered steps that install a package.
+///
+/// Every step that creates something records how to undo it, so a failed install removes the
+/// containers, network and files it created instead of leaving half a package behind.
+pub(crate) struct InstallPlan<'a> {
+    package: &'a Package,
+    steps: Vec<InstallStep>,
+}
+
+impl<'a> InstallPlan<'a> {
+    /// Plans the install of a package whose generated files live under `kittynode_path`.
+    pub(crate) fn new(package: &'a Package, kittynode_path: &Path) -> Self {
+        let mut steps = Vec::new();
+
+        let jwt_path = kittynode_path.join("jwt.hex");
+        if !jwt_path.exists() {
+            steps.push(InstallStep::GenerateJwtSecret(jwt_path.clone()));
+        }
+        let mut secrets: Vec<_> = package.secrets.values().collect();
+        secrets.sort_by(|a, b| a.path.cmp(&b.path));
+        for secret in secrets {
+            let path = PathBuf::from(&secret.path);
+            if !path.exists() {
+                steps.push(InstallStep::GenerateSecret {
+                    path,
+                    prefix: secret.prefix.clone(),
+                });
+            }
+        }
+        for container in &package.containers {
+            for binding in &container.file_bindings {
+                let source = PathBuf::from(&binding.source);
+                let step = InstallStep::CreateDirectory(source.clone());
+                let generated = source == jwt_path
+                    || package
+                        .secrets
+                        .values()
+                        .any(|secret| source == Path::new(&secret.path));
+                if source.starts_with(kittynode_path)
+                    && !generated
+                    && !source.exists()
+                    && !steps.contains(&step)
+                {
+                    steps.push(step);
+                }
+            }
+        }
+
+        steps.push(InstallStep::CreateNetwork(package.network_name.clone()));
+        for container in &package.containers {
+            steps.push(InstallStep::PullImage(container.name.clone()));
+            steps.push(InstallStep::CreateContainer(container.name.clone()));
+            steps.push(InstallStep::StartContainer(container.name.clone()));
+            steps.push(InstallStep::ConnectNetwork(container.name.clone()));
+        }
+
+        Self { package, steps }
+    }
+
+    /// Runs every step in order, returning the images the containers were created from.
+    ///
+    /// If a step fails, the completed steps are undone in reverse order and the error names the
+    /// failed step.
+    pub(crate) async fn execute(
+        &self,
+        runtime: &impl ContainerRuntime,
+        on_progress: OnPullProgress<'_>,
+    ) -> Result<ImageLock> {
+        let mut lock = ImageLock::default();
+        let mut created = Vec::new();
+
+        for step in &self.steps {
+            info!("Install step: {}", step);
+            match self.run_step(runtime, step, &mut lock, on_progress).await {
+                Ok(resource) => created.extend(resource),
+                Err(e) => {
+                    error!("Install step '{}' failed: {}", step, e);
+                    let failures = roll_back(runtime, created).await;
+                    let message = match failures {
+                        0 => format!("Failed to {step}, the install was rolled back"),
+                        n => format!(
+                            "Failed to {step}, and {n} rollback actions failed, see the logs"
+                        ),
+                    };
+                    return Err(e.wrap_err(message));
+                }
+            }
+        }
+
+        Ok(lock)
+    }
+
+    fn container(&self, name: &str) -> Result<&Container> {
+        self.package
+            .containers
+            .iter()
+            .find(|container| container.name == name)
+            .ok_or_else(|| eyre!("Container '{}' is not part of the package", name))
+    }
+
+    async fn run_step(
+        &self,
+        runtime: &impl ContainerRuntime,
+        step: &InstallStep,
+        lock: &mut ImageLock,
+        on_progress: OnPullProgress<'_>,
+    ) -> Result<Option<Created>> {
+        match step {
+            InstallStep::GenerateJwtSecret(path) => {
+                let directory = path
+                    .parent()
+                    .ok_or_else(|| eyre!("Invalid JWT secret path {:?}", path))?;
+                generate_jwt_secret_with_path(&directory.to_path_buf())?;
+                Ok(Some(Created::File(path.clone())))
+            }
+            InstallStep::GenerateSecret { path, prefix } => {
+                write_private_file(path, &generate_secret(prefix))?;
+                Ok(Some(Created::File(path.clone())))
+            }
+            InstallStep::CreateDirectory(path) => {
+                fs::create_dir_all(path)?;
+                Ok(Some(Created::Directory(path.clone())))
+            }
+            InstallStep::CreateNetwork(name) => {
+                // Remove network if it already exists
+                if runtime.network_exists(name).await? {
+                    runtime.remove_network(name).await?;
+                    info!("Removed existing network: '{}'", name);
+                }
+                runtime.create_network(name).await?;
+                Ok(Some(Created::Network(name.clone())))
+            }
+            InstallStep::PullImage(name) => {
+                let container = self.container(name)?;
+                let image = pull_image(runtime, container, on_progress).await?;
+                lock.containers.insert(name.clone(), image);
+                Ok(None)
+            }
+            InstallStep::CreateContainer(name) => {
+                let container = self.container(name)?;
+                runtime.create_container(container).await?;
+                Ok(Some(Created::Container(name.clone())))
+            }
+            InstallStep::StartContainer(name) => {
+                runtime.start_container(name).await?;
+                Ok(None)
+            }
+            InstallStep::ConnectNetwork(name) => {
+                runtime
+                    .connect_network(&self.package.network_name, name)
+                    .await?;
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Removes created resources in reverse order, returning how many could not be removed.
+async fn roll_back(runtime: &impl ContainerRuntime, created: Vec<Created>) -> usize {
+    let mut failures = 0;
+    for resource in created.into_iter().rev() {
+        warn!("Rolling back: removing {:?}", resource);
+        let result = match &resource {
+            Created::File(path) => fs::remove_file(path).map_err(Into::into),
+            Created::Directory(path) => fs::remove_dir_all(path).map_err(Into::into),
+            Created::Network(name) => runtime.remove_network(name).await,
+            Created::Container(name) => runtime.remove_container(name, None).await,
+        };
+        if let Err(e) = result {
+            error!("Failed to remove {:?}: {}", resource, e);
+            failures += 1;
+        }
+    }
+    failures
+}
+
+impl fmt::Display for InstallStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstallStep::GenerateJwtSecret(path) => {
+                write!(f, "generate JWT secret {}", path.display())
+            }
+            InstallStep::GenerateSecret { path, .. } => {
+                write!(f, "generate secret {}", path.display())
+            }
+            InstallStep::CreateDirectory(path) => {
+                write!(f, "create directory {}", path.display())
+            }
+            InstallStep::CreateNetwork(name) => write!(f, "create network '{name}'"),
+            InstallStep::PullImage(name) => write!(f, "pull image of container '{name}'"),
+            InstallStep::CreateContainer(name) => write!(f, "create container '{name}'"),
+            InstallStep::StartContainer(name) => write!(f, "start container '{name}'"),
+            InstallStep::ConnectNetwork(name) => {
+                write!(f, "connect container '{name}' to the network")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::manifest::{default_config, parse_manifest, render_package_with_root};
+    use tempfile::tempdir;
+
+    #[test]
+    fn plans_generated_files_before_containers() {
+        let temp_dir = tempdir().unwrap();
+        let manifest = parse_manifest(include_str!("../manifests/ethereum.toml")).unwrap();
+        let package =
+            render_package_with_root(&manifest, &default_config(&manifest), temp_dir.path())
+                .unwrap();
+
+        let plan = InstallPlan::new(&package, temp_dir.path());
+        let steps = &plan.steps;
+        assert_eq!(
+            steps[..3],
+            [
+                InstallStep::GenerateJwtSecret(temp_dir.path().join("jwt.hex")),
+                InstallStep::CreateDirectory(temp_dir.path().join(".lighthouse")),
+                InstallStep::CreateNetwork("ethereum-network".to_string()),
+            ]
+        );
+        assert_eq!(
+            steps.last(),
+            Some(&InstallStep::ConnectNetwork("lighthouse-node".to_string()))
+        );
+
+        fs::write(temp_dir.path().join("jwt.hex"), "secret").unwrap();
+        let plan = InstallPlan::new(&package, temp_dir.path());
+        assert!(
+            !plan
+                .steps
+                .iter()
+                .any(|step| matches!(step, InstallStep::GenerateJwtSecret(_)))
+        );
+    }
+}
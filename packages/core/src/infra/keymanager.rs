@@ -0,0 +1,283 @@
+use crate::domain::package::Package;
+use crate::domain::validator::{
+    KeystoreDeleteResult, KeystoreImport, KeystoreImportResult, ValidatorKey, keystore_pubkey,
+    validate_address, validate_pubkey,
+};
+use eyre::{Context, Result, bail, eyre};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::fs;
+use std::time::Duration;
+
+/// Imports decrypt every keystore, which takes a few seconds each.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// A client for the Keymanager API of a validator client.
+pub(crate) struct KeymanagerClient {
+    client: Client,
+    url: String,
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct Data<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct StatusMessage<S> {
+    status: S,
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct DeleteResponse<S> {
+    data: Vec<StatusMessage<S>>,
+    slashing_protection: String,
+}
+
+impl KeymanagerClient {
+    pub(crate) fn new(url: &str, token: &str) -> Result<Self> {
+        Ok(Self {
+            client: Client::builder().timeout(REQUEST_TIMEOUT).build()?,
+            url: url.trim_end_matches('/').to_string(),
+            token: token.trim().to_string(),
+        })
+    }
+
+    /// Connects to the validator client of a package, reading the API token it was installed
+    /// with.
+    pub(crate) fn for_package(package: &Package) -> Result<Self> {
+        let (Some(url), Some(secret)) = (
+            package.endpoints.get("keymanager_api"),
+            package.secrets.get("keymanager_token"),
+        ) else {
+            bail!(
+                "The validator client of package '{}' is not enabled",
+                package.name
+            );
+        };
+        let token = fs::read_to_string(&secret.path)
+            .wrap_err_with(|| format!("Failed to read the Keymanager API token {}", secret.path))?;
+        Self::new(url, &token)
+    }
+
+    pub(crate) async fn list_keystores(&self) -> Result<Vec<ValidatorKey>> {
+        let response: Data<Vec<ValidatorKey>> = self
+            .send(self.client.get(self.endpoint("keystores")))
+            .await?;
+        Ok(response.data)
+    }
+
+    /// Imports keystores, with the slashing protection interchange of the keys when they were
+    /// used in another validator client.
+    pub(crate) async fn import_keystores(
+        &self,
+        keystores: &[KeystoreImport],
+        slashing_protection: Option<&str>,
+    ) -> Result<Vec<KeystoreImportResult>> {
+        let pubkeys = keystores
+            .iter()
+            .map(|import| keystore_pubkey(&import.keystore))
+            .collect::<Result<Vec<_>>>()?;
+        let mut body = json!({
+            "keystores": keystores.iter().map(|import| &import.keystore).collect::<Vec<_>>(),
+            "passwords": keystores.iter().map(|import| &import.password).collect::<Vec<_>>(),
+        });
+        if let Some(slashing_protection) = slashing_protection {
+            body["slashing_protection"] = json!(slashing_protection);
+        }
+
+        let response: Data<Vec<StatusMessage<_>>> = self
+            .send(self.client.post(self.endpoint("keystores")).json(&body))
+            .await?;
+        Ok(pubkeys
+            .into_iter()
+            .zip(response.data)
+            .map(|(pubkey, result)| KeystoreImportResult {
+                pubkey,
+                status: result.status,
+                message: result.message,
+            })
+            .collect())
+    }
+
+    /// Removes keys, returning the result for each key and their slashing protection
+    /// interchange.
+    pub(crate) async fn delete_keystores(
+        &self,
+        pubkeys: &[String],
+    ) -> Result<(Vec<KeystoreDeleteResult>, String)> {
+        for pubkey in pubkeys {
+            validate_pubkey(pubkey)?;
+        }
+        let response: DeleteResponse<_> = self
+            .send(
+                self.client
+                    .delete(self.endpoint("keystores"))
+                    .json(&json!({ "pubkeys": pubkeys })),
+            )
+            .await?;
+        let results = pubkeys
+            .iter()
+            .zip(response.data)
+            .map(|(pubkey, result)| KeystoreDeleteResult {
+                pubkey: pubkey.clone(),
+                status: result.status,
+                message: result.message,
+            })
+            .collect();
+        Ok((results, response.slashing_protection))
+    }
+
+    pub(crate) async fn set_fee_recipient(&self, pubkey: &str, address: &str) -> Result<()> {
+        validate_pubkey(pubkey)?;
+        validate_address(address)?;
+        let url = self.endpoint(&format!("validator/{pubkey}/feerecipient"));
+        self.send_empty(
+            self.client
+                .post(url)
+                .json(&json!({ "ethaddress": address })),
+        )
+        .await
+    }
+
+    pub(crate) async fn set_graffiti(&self, pubkey: &str, graffiti: &str) -> Result<()> {
+        validate_pubkey(pubkey)?;
+        if graffiti.len() > 32 {
+            bail!("Graffiti must be at most 32 bytes");
+        }
+        let url = self.endpoint(&format!("validator/{pubkey}/graffiti"));
+        self.send_empty(self.client.post(url).json(&json!({ "graffiti": graffiti })))
+            .await
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/eth/v1/{}", self.url, path)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        let response = self.execute(request).await?;
+        response
+            .json()
+            .await
+            .wrap_err("Failed to decode the Keymanager API response")
+    }
+
+    async fn send_empty(&self, request: RequestBuilder) -> Result<()> {
+        self.execute(request).await.map(drop)
+    }
+
+    async fn execute(&self, request: RequestBuilder) -> Result<Response> {
+        let response = request
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .wrap_err("Failed to reach the Keymanager API")?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        #[derive(Deserialize)]
+        struct ErrorMessage {
+            message: String,
+        }
+        let body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<ErrorMessage>(&body)
+            .map(|error| error.message)
+            .unwrap_or(body);
+        Err(eyre!("Keymanager API returned {}: {}", status, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::validator::{DeleteStatus, ImportStatus};
+    use axum::{
+        Json, Router,
+        http::{HeaderMap, StatusCode},
+        routing::get,
+    };
+    use serde_json::Value;
+
+    const TOKEN: &str = "api-token-0x01";
+
+    fn authorized(headers: &HeaderMap) -> Result<(), StatusCode> {
+        match headers.get("authorization") {
+            Some(value) if value == &format!("Bearer {TOKEN}") => Ok(()),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+
+    /// Serves a validator client with a single loaded key.
+    async fn serve_stub_validator() -> String {
+        let app = Router::new().route(
+            "/eth/v1/keystores",
+            get(|headers: HeaderMap| async move {
+                authorized(&headers)?;
+                Ok::<_, StatusCode>(Json(json!({ "data": [{
+                    "validating_pubkey": format!("0x{}", "a".repeat(96)),
+                    "derivation_path": "m/12381/3600/0/0/0",
+                    "readonly": false
+                }]})))
+            })
+            .post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                authorized(&headers)?;
+                let results: Vec<_> = body["keystores"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|_| json!({ "status": "imported" }))
+                    .collect();
+                Ok::<_, StatusCode>(Json(json!({ "data": results })))
+            })
+            .delete(|headers: HeaderMap| async move {
+                authorized(&headers)?;
+                Ok::<_, StatusCode>(Json(json!({
+                    "data": [{ "status": "deleted" }, { "status": "not_found" }],
+                    "slashing_protection": "{\"metadata\":{},\"data\":[]}"
+                })))
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn manages_keystores() {
+        let url = serve_stub_validator().await;
+        let client = KeymanagerClient::new(&url, &format!("{TOKEN}\n")).unwrap();
+
+        let keys = client.list_keystores().await.unwrap();
+        assert_eq!(keys.len(), 1);
+
+        let keystore = KeystoreImport {
+            keystore: format!(r#"{{"pubkey": "{}"}}"#, "b".repeat(96)),
+            password: "password".to_string(),
+        };
+        let results = client.import_keystores(&[keystore], None).await.unwrap();
+        assert_eq!(results[0].pubkey, format!("0x{}", "b".repeat(96)));
+        assert_eq!(results[0].status, ImportStatus::Imported);
+
+        let pubkeys = [
+            format!("0x{}", "a".repeat(96)),
+            format!("0x{}", "b".repeat(96)),
+        ];
+        let (results, slashing_protection) = client.delete_keystores(&pubkeys).await.unwrap();
+        assert_eq!(results[0].status, DeleteStatus::Deleted);
+        assert_eq!(results[1].status, DeleteStatus::NotFound);
+        assert!(slashing_protection.contains("metadata"));
+
+        let unauthorized = KeymanagerClient::new(&url, "wrong").unwrap();
+        let error = unauthorized.list_keystores().await.unwrap_err();
+        assert!(error.to_string().contains("401"));
+    }
+}
@@ -0,0 +1,657 @@
+use crate::domain::condition::Condition;
+use crate::domain::container::{
+    Binding, Container, LogConfig, ResourceLimits, Ulimit, parse_cpus, parse_memory,
+};
+use crate::domain::manifest::{
+    ArgManifest, BindingManifest, ContainerManifest, PackageManifest, RequirementsManifest,
+};
+use crate::domain::package::{MetricsEndpoint, Monitoring, Package, PackageConfig, Secret};
+use crate::domain::requirements::Requirements;
+use crate::infra::file::kittynode_path;
+use crate::manifests::BUILTIN_MANIFESTS;
+use bollard::models::PortBinding;
+use eyre::{Context, Result, bail};
+use std::collections::HashMap;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::{info, warn};
+
+pub struct ManifestStore;
+
+impl ManifestStore {
+    /// Loads the built-in manifests followed by any TOML or JSON user manifests in
+    /// `.kittynode/manifests`.
+    ///
+    /// A user manifest replaces a built-in manifest with the same package name. User manifests
+    /// that fail to load are logged and skipped, so one bad file does not hide every package.
+    pub fn load_all() -> Result<Vec<PackageManifest>> {
+        let mut manifests = Vec::new();
+        for (file_name, contents) in BUILTIN_MANIFESTS {
+            let manifest = parse_manifest(contents)
+                .wrap_err_with(|| format!("Failed to load built-in manifest '{file_name}'"))?;
+            manifests.push(manifest);
+        }
+
+        let manifests_dir = Self::manifests_dir()?;
+        if manifests_dir.is_dir() {
+            let mut paths: Vec<PathBuf> = fs::read_dir(&manifests_dir)?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|ext| ext == "toml" || ext == "json")
+                })
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                let manifest = match load_manifest_file(&path) {
+                    Ok(manifest) => manifest,
+                    Err(e) => {
+                        warn!("Skipping manifest {}: {:#}", path.display(), e);
+                        continue;
+                    }
+                };
+                info!(
+                    "Loaded manifest '{}' from {}",
+                    manifest.name,
+                    path.display()
+                );
+                manifests.retain(|m: &PackageManifest| m.name != manifest.name);
+                manifests.push(manifest);
+            }
+        }
+
+        Ok(manifests)
+    }
+
+    /// Loads the manifest for a single package.
+    pub fn load(package_name: &str) -> Result<PackageManifest> {
+        Self::load_all()?
+            .into_iter()
+            .find(|manifest| manifest.name == package_name)
+            .ok_or_else(|| eyre::eyre!("Package '{}' not found", package_name))
+    }
+
+    /// Returns the directory user manifests are read from.
+    fn manifests_dir() -> Result<PathBuf> {
+        Ok(kittynode_path()?.join("manifests"))
+    }
+}
+
+/// Parses and validates a TOML manifest.
+pub(crate) fn parse_manifest(contents: &str) -> Result<PackageManifest> {
+    let manifest: PackageManifest = toml::from_str(contents)?;
+    manifest.validate()?;
+    Ok(manifest)
+}
+
+/// Parses and validates a JSON manifest, which has the same structure as a TOML one.
+pub(crate) fn parse_json_manifest(contents: &str) -> Result<PackageManifest> {
+    let manifest: PackageManifest = serde_json::from_str(contents)?;
+    manifest.validate()?;
+    Ok(manifest)
+}
+
+/// Loads a user manifest, parsed as JSON or TOML by its extension.
+fn load_manifest_file(path: &Path) -> Result<PackageManifest> {
+    let contents = fs::read_to_string(path)?;
+    let manifest = if path.extension().is_some_and(|ext| ext == "json") {
+        parse_json_manifest(&contents)
+    } else {
+        parse_manifest(&contents)
+    };
+    manifest.wrap_err_with(|| format!("Failed to load manifest {}", path.display()))
+}
+
+/// Returns the default config declared by a manifest.
+pub(crate) fn default_config(manifest: &PackageManifest) -> PackageConfig {
+    let mut config = PackageConfig::new();
+    for (key, entry) in &manifest.config {
+        config.values.insert(key.clone(), entry.default.clone());
+    }
+    config
+}
+
+/// Renders a manifest into a package using the given config on top of the manifest defaults.
+pub(crate) fn render_package(
+    manifest: &PackageManifest,
+    config: &PackageConfig,
+) -> Result<Package> {
+    render_package_with_root(manifest, config, &kittynode_path()?)
+}
+
+pub(crate) fn render_package_with_root(
+    manifest: &PackageManifest,
+    config: &PackageConfig,
+    kittynode_path: &std::path::Path,
+) -> Result<Package> {
+    let variables = template_variables(manifest, config, kittynode_path)?;
+    let mut containers = Vec::new();
+    for container in &manifest.containers {
+        if let Some(when) = &container.when
+            && !when.parse::<Condition>()?.evaluate(&variables)?
+        {
+            continue;
+        }
+        containers.push(
+            render_container(container, &variables)
+                .wrap_err_with(|| format!("Failed to render container '{}'", container.name))?,
+        );
+    }
+    let endpoints = manifest
+        .endpoints
+        .iter()
+        .map(|(name, url)| {
+            render_template(url, &variables)
+                .map(|url| (name.clone(), url))
+                .wrap_err_with(|| format!("Failed to render endpoint '{name}'"))
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+    let mut secrets = HashMap::new();
+    for (name, secret) in &manifest.secrets {
+        if let Some(when) = &secret.when
+            && !when.parse::<Condition>()?.evaluate(&variables)?
+        {
+            continue;
+        }
+        let path = render_template(&secret.path, &variables)
+            .wrap_err_with(|| format!("Failed to render secret '{name}'"))?;
+        secrets.insert(
+            name.clone(),
+            Secret {
+                path,
+                prefix: secret.prefix.clone(),
+            },
+        );
+    }
+    let metrics = manifest
+        .metrics
+        .iter()
+        .filter(|endpoint| containers.iter().any(|c| c.name == endpoint.container))
+        .map(|endpoint| {
+            let host_url = endpoint
+                .host_url
+                .as_ref()
+                .map(|url| render_template(url, &variables))
+                .transpose()
+                .wrap_err_with(|| format!("Failed to render metrics job '{}'", endpoint.job))?;
+            Ok(MetricsEndpoint {
+                job: endpoint.job.clone(),
+                container: endpoint.container.clone(),
+                port: endpoint.port,
+                path: endpoint.path.clone(),
+                host_url,
+                series: endpoint.series.clone(),
+            })
+        })
+        .collect::<Result<_>>()?;
+    let monitoring = manifest
+        .monitoring
+        .as_ref()
+        .map(|monitoring| {
+            render_template(&monitoring.config_dir, &variables).map(|config_dir| Monitoring {
+                prometheus_container: monitoring.prometheus_container.clone(),
+                config_dir,
+            })
+        })
+        .transpose()
+        .wrap_err("Failed to render the monitoring config directory")?;
+
+    Ok(Package {
+        name: manifest.name.clone(),
+        description: manifest.description.clone(),
+        network_name: manifest.network_name.clone(),
+        containers,
+        endpoints,
+        secrets,
+        metrics,
+        monitoring,
+        requirements: render_requirements(&manifest.requirements, &variables)
+            .wrap_err("Failed to render requirements")?,
+        default_config: default_config(manifest),
+        config_schema: manifest.config_schema(),
+    })
+}
+
+/// Builds the variables available to templates.
+///
+/// Every config key is available by name, and each attribute of the selected value of a config
+/// key is available as `key.attribute`.
+fn template_variables(
+    manifest: &PackageManifest,
+    config: &PackageConfig,
+    kittynode_path: &std::path::Path,
+) -> Result<HashMap<String, String>> {
+    let mut variables = HashMap::from([(
+        "kittynode_path".to_string(),
+        kittynode_path.display().to_string(),
+    )]);
+
+    for (key, entry) in &manifest.config {
+        let value = config.values.get(key).unwrap_or(&entry.default);
+        variables.insert(key.clone(), value.clone());
+    }
+
+    // Attributes are templates over the config keys, so a value such as a custom network can
+    // take its attributes from other keys
+    let mut attributes = HashMap::new();
+    for (key, entry) in &manifest.config {
+        if entry.values.is_empty() {
+            continue;
+        }
+        let value = &variables[key];
+        let Some(value_attributes) = entry.values.get(value) else {
+            bail!(
+                "'{}' is not a valid value for '{}', expected one of: {}",
+                value,
+                key,
+                entry.values.keys().cloned().collect::<Vec<_>>().join(", ")
+            );
+        };
+        for (attribute, attribute_value) in value_attributes {
+            attributes.insert(
+                format!("{key}.{attribute}"),
+                render_template(attribute_value, &variables).wrap_err_with(|| {
+                    format!("Failed to render attribute '{attribute}' of '{key}'")
+                })?,
+            );
+        }
+    }
+    variables.extend(attributes);
+
+    Ok(variables)
+}
+
+fn render_container(
+    container: &ContainerManifest,
+    variables: &HashMap<String, String>,
+) -> Result<Container> {
+    let mut port_bindings: HashMap<String, Vec<PortBinding>> = HashMap::new();
+    for port in &container.ports {
+        port_bindings
+            .entry(render_template(&port.container_port, variables)?)
+            .or_default()
+            .push(PortBinding {
+                host_ip: Some(render_template(&port.host_ip, variables)?),
+                host_port: Some(render_template(&port.host_port, variables)?),
+            });
+    }
+
+    Ok(Container {
+        name: render_template(&container.name, variables)?,
+        image: render_template(&container.image, variables)?,
+        tag: render_template(&container.tag, variables)?,
+        digest: container
+            .digest
+            .as_deref()
+            .map(|digest| render_template(digest, variables))
+            .transpose()?
+            .filter(|digest| !digest.is_empty()),
+        cmd: render_args(&container.cmd, variables)?,
+        port_bindings,
+        volume_bindings: render_bindings(&container.volumes, variables)?,
+        file_bindings: render_bindings(&container.files, variables)?,
+        restart_policy: container
+            .restart
+            .as_deref()
+            .map(|restart| render_template(restart, variables)?.parse())
+            .transpose()?
+            .unwrap_or_default(),
+        limits: ResourceLimits {
+            memory_bytes: render_limit(
+                container.limits.memory.as_deref(),
+                variables,
+                parse_memory,
+            )?,
+            nano_cpus: render_limit(container.limits.cpus.as_deref(), variables, parse_cpus)?,
+        },
+        ulimits: container
+            .ulimits
+            .iter()
+            .map(|ulimit| Ulimit {
+                name: ulimit.name.clone(),
+                soft: ulimit.soft,
+                hard: ulimit.hard,
+            })
+            .collect(),
+        stop_signal: container.stop_signal.clone(),
+        stop_timeout_secs: container.stop_timeout.map(i64::from),
+        log_config: container
+            .log
+            .as_ref()
+            .map(|log| -> Result<LogConfig> {
+                Ok(LogConfig {
+                    driver: render_template(&log.driver, variables)?,
+                    options: log
+                        .options
+                        .iter()
+                        .map(|(key, value)| Ok((key.clone(), render_template(value, variables)?)))
+                        .collect::<Result<_>>()?,
+                })
+            })
+            .transpose()?,
+    })
+}
+
+fn render_limit(
+    limit: Option<&str>,
+    variables: &HashMap<String, String>,
+    parse: fn(&str) -> Result<Option<i64>>,
+) -> Result<Option<i64>> {
+    match limit {
+        Some(limit) => parse(&render_template(limit, variables)?),
+        None => Ok(None),
+    }
+}
+
+fn render_requirements(
+    requirements: &RequirementsManifest,
+    variables: &HashMap<String, String>,
+) -> Result<Requirements> {
+    fn render<T: std::str::FromStr>(
+        name: &str,
+        value: Option<&String>,
+        variables: &HashMap<String, String>,
+    ) -> Result<Option<T>> {
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let rendered = render_template(value, variables)?;
+        rendered
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| eyre::eyre!("Requirement '{}' is not a number: '{}'", name, rendered))
+    }
+
+    Ok(Requirements {
+        min_cores: render("min_cores", requirements.min_cores.as_ref(), variables)?,
+        recommended_cores: render(
+            "recommended_cores",
+            requirements.recommended_cores.as_ref(),
+            variables,
+        )?,
+        min_memory_gb: render(
+            "min_memory_gb",
+            requirements.min_memory_gb.as_ref(),
+            variables,
+        )?,
+        recommended_memory_gb: render(
+            "recommended_memory_gb",
+            requirements.recommended_memory_gb.as_ref(),
+            variables,
+        )?,
+        min_disk_gb: render("min_disk_gb", requirements.min_disk_gb.as_ref(), variables)?,
+        recommended_disk_gb: render(
+            "recommended_disk_gb",
+            requirements.recommended_disk_gb.as_ref(),
+            variables,
+        )?,
+    })
+}
+
+/// Renders command line arguments, leaving out groups whose condition does not hold.
+fn render_args(args: &[ArgManifest], variables: &HashMap<String, String>) -> Result<Vec<String>> {
+    let mut rendered = Vec::new();
+    for arg in args {
+        match arg {
+            ArgManifest::Arg(arg) => rendered.push(render_template(arg, variables)?),
+            ArgManifest::Group(group) => {
+                if group.when.parse::<Condition>()?.evaluate(variables)? {
+                    for arg in &group.args {
+                        rendered.push(render_template(arg, variables)?);
+                    }
+                }
+            }
+        }
+    }
+    Ok(rendered)
+}
+
+fn render_bindings(
+    bindings: &[BindingManifest],
+    variables: &HashMap<String, String>,
+) -> Result<Vec<Binding>> {
+    let mut included = Vec::new();
+    for binding in bindings {
+        let when = binding
+            .when
+            .as_deref()
+            .map(str::parse::<Condition>)
+            .transpose()?;
+        if when.map_or(Ok(true), |when| when.evaluate(variables))? {
+            included.push(binding);
+        }
+    }
+
+    included
+        .into_iter()
+        .map(|binding| {
+            Ok(Binding {
+                source: render_template(&binding.source, variables)?,
+                destination: render_template(&binding.destination, variables)?,
+                options: binding
+                    .options
+                    .as_deref()
+                    .map(|options| render_template(options, variables))
+                    .transpose()?,
+                persist: binding.persist,
+            })
+        })
+        .collect()
+}
+
+/// Replaces every `{{name}}` placeholder in the template with its variable.
+fn render_template(template: &str, variables: &HashMap<String, String>) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            bail!("Unclosed placeholder in '{}'", template);
+        };
+        let name = after_open[..end].trim();
+        let value = variables
+            .get(name)
+            .ok_or_else(|| eyre::eyre!("Unknown variable '{}' in '{}'", name, template))?;
+        rendered.push_str(value);
+        rest = &after_open[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::container::RestartPolicy;
+    use std::path::Path;
+
+    fn ethereum_manifest() -> PackageManifest {
+        parse_manifest(include_str!("../manifests/ethereum.toml")).unwrap()
+    }
+
+    #[test]
+    fn builtin_manifests_are_valid() {
+        for (file_name, contents) in BUILTIN_MANIFESTS {
+            let result = parse_manifest(contents);
+            assert!(result.is_ok(), "{file_name}: {result:?}");
+        }
+    }
+
+    #[test]
+    fn parses_json_manifests() {
+        let toml: toml::Value = toml::from_str(include_str!("../manifests/ethereum.toml")).unwrap();
+        let json = serde_json::to_string(&toml).unwrap();
+        let manifest = parse_json_manifest(&json).unwrap();
+        assert_eq!(manifest.name, ethereum_manifest().name);
+        assert_eq!(
+            manifest.containers.len(),
+            ethereum_manifest().containers.len()
+        );
+        assert!(parse_json_manifest("{\"name\": \"broken\"}").is_err());
+    }
+
+    #[test]
+    fn renders_ethereum_with_config() {
+        let mut config = PackageConfig::new();
+        config
+            .values
+            .insert("network".to_string(), "mainnet".to_string());
+
+        let package =
+            render_package_with_root(&ethereum_manifest(), &config, Path::new("/home/kitty"))
+                .unwrap();
+
+        let lighthouse = &package.containers[1];
+        assert_eq!(lighthouse.name, "lighthouse-node");
+        assert!(
+            lighthouse
+                .cmd
+                .contains(&"https://mainnet.checkpoint.sigp.io/".to_string())
+        );
+        assert_eq!(
+            lighthouse.file_bindings[1].source,
+            "/home/kitty/jwt.hex".to_string()
+        );
+        assert_eq!(package.requirements.min_disk_gb, Some(2000));
+        assert_eq!(
+            package.endpoints.get("consensus_api"),
+            Some(&"http://127.0.0.1:5052".to_string())
+        );
+        assert_eq!(
+            package.default_config.values.get("network"),
+            Some(&"holesky".to_string())
+        );
+        assert_eq!(
+            package.config_schema.field("network").unwrap().allowed,
+            ["custom", "holesky", "hoodi", "mainnet", "sepolia"]
+        );
+    }
+
+    #[test]
+    fn renders_custom_network_from_config() {
+        let mut config = PackageConfig::new();
+        for (key, value) in [
+            ("network", "custom"),
+            ("custom_network_dir", "/home/kitty/devnet"),
+            ("execution_bootnodes", "enode://a@127.0.0.1:30303"),
+        ] {
+            config.values.insert(key.to_string(), value.to_string());
+        }
+
+        let package =
+            render_package_with_root(&ethereum_manifest(), &config, Path::new("/home/kitty"))
+                .unwrap();
+
+        let reth = &package.containers[0];
+        assert_eq!(reth.cmd[..3], ["node", "--chain", "/network/genesis.json"]);
+        assert!(reth.cmd.ends_with(&[
+            "--bootnodes".to_string(),
+            "enode://a@127.0.0.1:30303".to_string()
+        ]));
+        assert!(
+            reth.file_bindings
+                .iter()
+                .any(|binding| binding.source == "/home/kitty/devnet")
+        );
+
+        let lighthouse = &package.containers[1];
+        assert_eq!(
+            lighthouse.cmd[..3],
+            ["lighthouse", "--testnet-dir", "/network"]
+        );
+        assert!(
+            lighthouse
+                .cmd
+                .contains(&"--allow-insecure-genesis-sync".to_string())
+        );
+        assert!(
+            !lighthouse
+                .cmd
+                .contains(&"--checkpoint-sync-url".to_string())
+        );
+        assert!(!lighthouse.cmd.contains(&"--boot-nodes".to_string()));
+    }
+
+    #[test]
+    fn renders_the_chosen_clients() {
+        let manifest = ethereum_manifest();
+        let mut config = PackageConfig::new();
+        for (key, value) in [
+            ("execution_client", "geth"),
+            ("consensus_client", "teku"),
+            ("restart_policy", "always"),
+            ("execution_memory_limit", "16g"),
+        ] {
+            config.values.insert(key.to_string(), value.to_string());
+        }
+
+        let package =
+            render_package_with_root(&manifest, &config, Path::new("/home/kitty")).unwrap();
+        let names: Vec<_> = package.containers.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["geth-node", "teku-node"]);
+        assert_eq!(package.containers[0].cmd[0], "--holesky");
+        assert!(
+            package.containers[1]
+                .cmd
+                .contains(&"--ee-endpoint=http://geth-node:8551".to_string())
+        );
+        let geth = &package.containers[0];
+        assert_eq!(geth.restart_policy, RestartPolicy::Always);
+        assert_eq!(geth.limits.memory_bytes, Some(16 << 30));
+        assert_eq!(geth.limits.nano_cpus, None);
+        assert_eq!(geth.stop_timeout_secs, Some(300));
+        assert_eq!(
+            geth.log_config.as_ref().unwrap().options["max-size"],
+            "100m"
+        );
+        for container in &package.containers {
+            assert!(
+                container
+                    .file_bindings
+                    .iter()
+                    .any(|binding| binding.source == "/home/kitty/jwt.hex")
+            );
+        }
+
+        config
+            .values
+            .insert("network".to_string(), "custom".to_string());
+        config
+            .values
+            .insert("custom_network_dir".to_string(), "/devnet".to_string());
+        let error = manifest
+            .config_schema()
+            .validate("Ethereum", &config)
+            .unwrap_err();
+        assert_eq!(error.errors[0].key, "execution_client");
+    }
+
+    #[test]
+    fn rejects_unknown_config_value() {
+        let mut config = PackageConfig::new();
+        config
+            .values
+            .insert("network".to_string(), "foo".to_string());
+
+        let result = render_package_with_root(&ethereum_manifest(), &config, Path::new("/"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_template_variable() {
+        let variables = HashMap::from([("network".to_string(), "holesky".to_string())]);
+        assert_eq!(
+            render_template("--chain={{ network }}", &variables).unwrap(),
+            "--chain=holesky"
+        );
+        assert!(render_template("{{netwrok}}", &variables).is_err());
+        assert!(render_template("{{network", &variables).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::domain::metrics::MetricsSnapshot;
+use crate::infra::file::{kittynode_path, write_file_atomically};
+use eyre::Result;
+use std::{fs, io::ErrorKind, path::PathBuf};
+
+pub struct MetricsHistoryStore;
+
+impl MetricsHistoryStore {
+    /// Loads the recorded metrics snapshots of a package, oldest first.
+    pub fn load(package_name: &str) -> Result<Vec<MetricsSnapshot>> {
+        let history_path = Self::history_file_path(package_name)?;
+        if !history_path.exists() {
+            return Ok(Vec::new());
+        }
+        let json = fs::read_to_string(history_path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn save(package_name: &str, snapshots: &[MetricsSnapshot]) -> Result<()> {
+        let history_path = Self::history_file_path(package_name)?;
+        write_file_atomically(&history_path, &serde_json::to_string(snapshots)?)
+    }
+
+    pub fn remove(package_name: &str) -> Result<()> {
+        match fs::remove_file(Self::history_file_path(package_name)?) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn history_file_path(package_name: &str) -> Result<PathBuf> {
+        Ok(kittynode_path()?
+            .join("packages")
+            .join(package_name)
+            .join("metrics.json"))
+    }
+}
@@ -1,5 +1,21 @@
+pub mod auth;
 pub mod config;
 pub mod docker;
+#[cfg(test)]
+pub mod fake_runtime;
 pub mod file;
+pub mod image_lock;
+pub mod install_plan;
+pub mod keymanager;
+pub mod manifest;
+pub mod metrics_history;
+pub mod monitoring;
+pub mod node_status;
 pub mod package;
 pub mod package_config;
+pub mod ports;
+pub mod prometheus;
+pub mod pull_progress;
+pub mod runtime;
+pub mod tls;
+pub mod validator_store;
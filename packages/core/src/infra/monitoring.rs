@@ -0,0 +1,213 @@
+use crate::domain::package::{Monitoring, Package};
+use crate::infra::runtime::ContainerRuntime;
+use crate::manifests::BUILTIN_DASHBOARDS;
+use eyre::{Context, Result};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+/// Tells Grafana to load the dashboards Kittynode writes.
+const DASHBOARD_PROVIDER: &str = "\
+apiVersion: 1
+providers:
+  - name: kittynode
+    type: file
+    options:
+      path: /etc/grafana/dashboards
+";
+
+/// Writes the Prometheus scrape config for the metrics endpoints of `packages`, along with the
+/// Grafana datasource and dashboards.
+pub(crate) fn write_monitoring_config(monitoring: &Monitoring, packages: &[Package]) -> Result<()> {
+    let dir = Path::new(&monitoring.config_dir);
+    write_file(
+        &dir.join("prometheus/prometheus.yml"),
+        &scrape_config(packages),
+    )?;
+    write_file(
+        &dir.join("grafana/provisioning/datasources/prometheus.yml"),
+        &datasource(monitoring),
+    )?;
+    write_file(
+        &dir.join("grafana/provisioning/dashboards/kittynode.yml"),
+        DASHBOARD_PROVIDER,
+    )?;
+    for (file_name, dashboard) in BUILTIN_DASHBOARDS {
+        write_file(&dir.join("grafana/dashboards").join(file_name), dashboard)?;
+    }
+    Ok(())
+}
+
+/// Connects Prometheus to the network of every package it scrapes.
+pub(crate) async fn connect_prometheus(
+    runtime: &impl ContainerRuntime,
+    monitoring: &Monitoring,
+    packages: &[Package],
+) -> Result<()> {
+    let connected = runtime
+        .container_networks(&monitoring.prometheus_container)
+        .await?;
+    for package in packages.iter().filter(|p| !p.metrics.is_empty()) {
+        if connected.contains(&package.network_name) {
+            continue;
+        }
+        info!(
+            "Connecting '{}' to network '{}'",
+            monitoring.prometheus_container, package.network_name
+        );
+        runtime
+            .connect_network(&package.network_name, &monitoring.prometheus_container)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Points the monitoring packages among `installed` at the metrics endpoints of the other
+/// installed packages, restarting Prometheus so it reloads its config.
+pub(crate) async fn refresh_monitoring(
+    runtime: &impl ContainerRuntime,
+    installed: &[Package],
+) -> Result<()> {
+    for package in installed {
+        let Some(monitoring) = &package.monitoring else {
+            continue;
+        };
+        let scraped: Vec<_> = installed
+            .iter()
+            .filter(|p| p.name != package.name)
+            .cloned()
+            .collect();
+        write_monitoring_config(monitoring, &scraped)?;
+        connect_prometheus(runtime, monitoring, &scraped).await?;
+        runtime
+            .stop_container(&monitoring.prometheus_container, None)
+            .await?;
+        runtime
+            .start_container(&monitoring.prometheus_container)
+            .await?;
+        info!("Updated the scrape config of package '{}'", package.name);
+    }
+    Ok(())
+}
+
+/// Disconnects the Prometheus containers of the monitoring packages among `installed` from a
+/// network, so the network can be removed.
+pub(crate) async fn disconnect_prometheus(
+    runtime: &impl ContainerRuntime,
+    installed: &[Package],
+    network_name: &str,
+) -> Result<()> {
+    for monitoring in installed.iter().filter_map(|p| p.monitoring.as_ref()) {
+        let container = &monitoring.prometheus_container;
+        if runtime
+            .container_networks(container)
+            .await?
+            .iter()
+            .any(|network| network == network_name)
+        {
+            runtime.disconnect_network(network_name, container).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Renders a Prometheus config with a job for each metrics endpoint, labelled with its package.
+fn scrape_config(packages: &[Package]) -> String {
+    // JSON strings are valid YAML, which keeps the values quoted
+    let quote = |value: &str| serde_json::to_string(value).unwrap_or_default();
+
+    let mut config = String::from(
+        "global:\n  scrape_interval: 15s\n\nscrape_configs:\n  - job_name: prometheus\n    static_configs:\n      - targets: [\"localhost:9090\"]\n",
+    );
+    for package in packages {
+        for endpoint in &package.metrics {
+            let target = format!("{}:{}", endpoint.container, endpoint.port);
+            let _ = write!(
+                config,
+                "  - job_name: {}\n    metrics_path: {}\n    static_configs:\n      - targets: [{}]\n        labels:\n          package: {}\n",
+                quote(&endpoint.job),
+                quote(&endpoint.path),
+                quote(&target),
+                quote(&package.name),
+            );
+        }
+    }
+    config
+}
+
+/// Renders the Grafana datasource, which reaches Prometheus on the monitoring network.
+fn datasource(monitoring: &Monitoring) -> String {
+    format!(
+        "apiVersion: 1\ndatasources:\n  - name: Prometheus\n    uid: prometheus\n    type: prometheus\n    access: proxy\n    url: http://{}:9090\n    isDefault: true\n",
+        monitoring.prometheus_container
+    )
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents).wrap_err_with(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infra::fake_runtime::FakeRuntime;
+    use crate::infra::manifest::{default_config, parse_manifest, render_package_with_root};
+    use crate::infra::package::install_package;
+    use tempfile::tempdir;
+
+    fn render(manifest: &str, root: &Path) -> Package {
+        let manifest = parse_manifest(manifest).unwrap();
+        render_package_with_root(&manifest, &default_config(&manifest), root).unwrap()
+    }
+
+    #[tokio::test]
+    async fn scrapes_the_installed_packages() {
+        let temp_dir = tempdir().unwrap();
+        let ethereum = render(include_str!("../manifests/ethereum.toml"), temp_dir.path());
+        let monitoring = render(
+            include_str!("../manifests/monitoring.toml"),
+            temp_dir.path(),
+        );
+        let runtime = FakeRuntime::new();
+        for package in [&ethereum, &monitoring] {
+            install_package(&runtime, package, temp_dir.path(), &|_| {})
+                .await
+                .unwrap();
+        }
+
+        let installed = [ethereum.clone(), monitoring.clone()];
+        refresh_monitoring(&runtime, &installed).await.unwrap();
+
+        let config =
+            fs::read_to_string(temp_dir.path().join("monitoring/prometheus/prometheus.yml"))
+                .unwrap();
+        assert!(config.contains("job_name: \"reth\""));
+        assert!(config.contains("targets: [\"reth-node:9001\"]"));
+        assert!(config.contains("targets: [\"lighthouse-node:5054\"]"));
+        assert!(!config.contains("geth-node"));
+        assert!(
+            temp_dir
+                .path()
+                .join("monitoring/grafana/dashboards/ethereum.json")
+                .exists()
+        );
+        assert!(
+            runtime.state().containers["prometheus"]
+                .networks
+                .contains("ethereum-network")
+        );
+
+        disconnect_prometheus(&runtime, &installed, "ethereum-network")
+            .await
+            .unwrap();
+        assert!(
+            !runtime.state().containers["prometheus"]
+                .networks
+                .contains("ethereum-network")
+        );
+    }
+}
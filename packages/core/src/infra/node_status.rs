@@ -0,0 +1,304 @@
+use crate::domain::node_status::{
+    ConsensusClientStatus, ConsensusHealth, EthereumNodeStatus, ExecutionClientStatus,
+    MevBoostStatus, RelayStatus,
+};
+use eyre::{Context, Result, bail};
+use reqwest::{Client, StatusCode, Url};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub(crate) fn http_client() -> Result<Client> {
+    Ok(Client::builder().timeout(REQUEST_TIMEOUT).build()?)
+}
+
+/// Queries the execution client JSON-RPC API and the consensus client beacon API.
+pub(crate) async fn get_ethereum_node_status(
+    client: &Client,
+    execution_rpc: &str,
+    consensus_api: &str,
+) -> EthereumNodeStatus {
+    let execution = get_execution_status(client, execution_rpc)
+        .await
+        .unwrap_or_else(|e| ExecutionClientStatus {
+            error: Some(format!("{e:#}")),
+            ..Default::default()
+        });
+    let consensus = get_consensus_status(client, consensus_api)
+        .await
+        .unwrap_or_else(|e| ConsensusClientStatus {
+            error: Some(format!("{e:#}")),
+            ..Default::default()
+        });
+    EthereumNodeStatus::new(execution, consensus)
+}
+
+/// Queries the builder API status of MEV-Boost and of each of its relays.
+pub(crate) async fn get_mev_boost_status(
+    client: &Client,
+    mev_boost_api: &str,
+    relays: &[String],
+) -> MevBoostStatus {
+    let error = |result: Result<()>| result.err().map(|e| format!("{e:#}"));
+
+    let mut relay_statuses = Vec::new();
+    for relay in relays {
+        let relay_error = error(get_builder_status(client, relay).await);
+        relay_statuses.push(RelayStatus {
+            url: relay.clone(),
+            reachable: relay_error.is_none(),
+            error: relay_error,
+        });
+    }
+
+    let mev_boost_error = error(get_builder_status(client, mev_boost_api).await);
+    MevBoostStatus {
+        reachable: mev_boost_error.is_none(),
+        relays: relay_statuses,
+        error: mev_boost_error,
+    }
+}
+
+async fn get_execution_status(client: &Client, url: &str) -> Result<ExecutionClientStatus> {
+    let syncing = rpc_call(client, url, "eth_syncing").await?;
+    let block_number = parse_quantity(&rpc_call(client, url, "eth_blockNumber").await?)?;
+    let peers = parse_quantity(&rpc_call(client, url, "net_peerCount").await?)?;
+
+    let (is_syncing, current_block, highest_block) = match &syncing {
+        Value::Object(progress) => (
+            true,
+            progress
+                .get("currentBlock")
+                .map(parse_quantity)
+                .transpose()?,
+            progress
+                .get("highestBlock")
+                .map(parse_quantity)
+                .transpose()?,
+        ),
+        _ => (false, Some(block_number), Some(block_number)),
+    };
+
+    Ok(ExecutionClientStatus {
+        reachable: true,
+        is_syncing,
+        current_block,
+        highest_block,
+        peers: Some(peers),
+        error: None,
+    })
+}
+
+async fn get_consensus_status(client: &Client, url: &str) -> Result<ConsensusClientStatus> {
+    #[derive(Deserialize)]
+    struct Syncing {
+        #[serde(deserialize_with = "string_u64")]
+        head_slot: u64,
+        #[serde(deserialize_with = "string_u64")]
+        sync_distance: u64,
+        is_syncing: bool,
+        #[serde(default)]
+        is_optimistic: bool,
+        el_offline: Option<bool>,
+    }
+
+    #[derive(Deserialize)]
+    struct PeerCount {
+        #[serde(deserialize_with = "string_u64")]
+        connected: u64,
+    }
+
+    let health = match client
+        .get(format!("{url}/eth/v1/node/health"))
+        .send()
+        .await?
+        .status()
+    {
+        StatusCode::OK => ConsensusHealth::Ready,
+        StatusCode::PARTIAL_CONTENT => ConsensusHealth::Syncing,
+        StatusCode::SERVICE_UNAVAILABLE => ConsensusHealth::NotInitialized,
+        _ => ConsensusHealth::Unknown,
+    };
+    let syncing: Syncing = beacon_get(client, &format!("{url}/eth/v1/node/syncing")).await?;
+    let peers: PeerCount = beacon_get(client, &format!("{url}/eth/v1/node/peer_count")).await?;
+
+    Ok(ConsensusClientStatus {
+        reachable: true,
+        health,
+        head_slot: Some(syncing.head_slot),
+        sync_distance: Some(syncing.sync_distance),
+        is_syncing: syncing.is_syncing,
+        is_optimistic: syncing.is_optimistic,
+        el_offline: syncing.el_offline,
+        peers: Some(peers.connected),
+        error: None,
+    })
+}
+
+/// Checks the `/eth/v1/builder/status` endpoint served by both MEV-Boost and relays.
+async fn get_builder_status(client: &Client, url: &str) -> Result<()> {
+    let mut url = Url::parse(url).wrap_err_with(|| format!("Invalid URL '{url}'"))?;
+    // Relay URLs carry the relay public key as the user name, which is not a credential
+    let _ = url.set_username("");
+    url.set_path("/eth/v1/builder/status");
+    client.get(url).send().await?.error_for_status()?;
+    Ok(())
+}
+
+/// Calls a JSON-RPC method without parameters and returns its result.
+async fn rpc_call(client: &Client, url: &str, method: &str) -> Result<Value> {
+    let response: Value = client
+        .post(url)
+        .json(&json!({ "jsonrpc": "2.0", "method": method, "params": [], "id": 1 }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .wrap_err_with(|| format!("Invalid response to {method}"))?;
+
+    if let Some(error) = response.get("error") {
+        bail!("{} failed: {}", method, error);
+    }
+    response
+        .get("result")
+        .cloned()
+        .ok_or_else(|| eyre::eyre!("{} returned no result", method))
+}
+
+/// Fetches a beacon API endpoint and returns its `data` field.
+async fn beacon_get<T: DeserializeOwned>(client: &Client, url: &str) -> Result<T> {
+    #[derive(Deserialize)]
+    struct Envelope<T> {
+        data: T,
+    }
+
+    let envelope: Envelope<T> = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .wrap_err_with(|| format!("Invalid response from {url}"))?;
+    Ok(envelope.data)
+}
+
+/// Parses a hex encoded JSON-RPC quantity such as `"0x1b4"`.
+fn parse_quantity(value: &Value) -> Result<u64> {
+    let quantity = value
+        .as_str()
+        .and_then(|s| s.strip_prefix("0x"))
+        .ok_or_else(|| eyre::eyre!("Expected a hex quantity, got {}", value))?;
+    Ok(u64::from_str_radix(quantity, 16)?)
+}
+
+/// Deserializes a `u64` the beacon API encodes as a decimal string.
+fn string_u64<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Json, Router, http::StatusCode, routing::get, routing::post};
+
+    /// Serves canned execution and consensus client responses on a local port.
+    async fn serve_stub_node() -> String {
+        async fn rpc(Json(request): Json<Value>) -> Json<Value> {
+            let result = match request["method"].as_str() {
+                Some("eth_syncing") => json!({ "currentBlock": "0x10", "highestBlock": "0x20" }),
+                Some("eth_blockNumber") => json!("0x10"),
+                Some("net_peerCount") => json!("0x5"),
+                _ => Value::Null,
+            };
+            Json(json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+        }
+
+        let app = Router::new()
+            .route("/", post(rpc))
+            .route("/eth/v1/builder/status", get(|| async { StatusCode::OK }))
+            .route(
+                "/eth/v1/node/health",
+                get(|| async { StatusCode::PARTIAL_CONTENT }),
+            )
+            .route(
+                "/eth/v1/node/syncing",
+                get(|| async {
+                    Json(json!({ "data": {
+                        "head_slot": "1000",
+                        "sync_distance": "24",
+                        "is_syncing": true,
+                        "is_optimistic": false,
+                        "el_offline": false
+                    }}))
+                }),
+            )
+            .route(
+                "/eth/v1/node/peer_count",
+                get(|| async {
+                    Json(json!({ "data": {
+                        "disconnected": "1",
+                        "connecting": "0",
+                        "connected": "42",
+                        "disconnecting": "0"
+                    }}))
+                }),
+            );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn reads_status_from_both_clients() {
+        let url = serve_stub_node().await;
+        let status = get_ethereum_node_status(&http_client().unwrap(), &url, &url).await;
+
+        assert!(status.execution.reachable);
+        assert!(status.execution.is_syncing);
+        assert_eq!(status.execution.current_block, Some(16));
+        assert_eq!(status.execution.highest_block, Some(32));
+        assert_eq!(status.execution.peers, Some(5));
+
+        assert!(status.consensus.reachable);
+        assert_eq!(status.consensus.health, ConsensusHealth::Syncing);
+        assert_eq!(status.consensus.head_slot, Some(1000));
+        assert_eq!(status.consensus.sync_distance, Some(24));
+        assert_eq!(status.consensus.peers, Some(42));
+        assert!(status.clients_in_agreement);
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_clients() {
+        let url = serve_stub_node().await;
+        let status =
+            get_ethereum_node_status(&http_client().unwrap(), "http://127.0.0.1:1", &url).await;
+
+        assert!(!status.execution.reachable);
+        assert!(status.execution.error.is_some());
+        assert!(status.consensus.reachable);
+        assert!(!status.clients_in_agreement);
+    }
+
+    #[tokio::test]
+    async fn reports_relay_status() {
+        let url = serve_stub_node().await;
+        let relays = [
+            url.replace("http://", &format!("http://0x{}@", "a".repeat(96))),
+            "http://127.0.0.1:1".to_string(),
+        ];
+        let status = get_mev_boost_status(&http_client().unwrap(), &url, &relays).await;
+
+        assert!(status.reachable);
+        assert!(status.relays[0].reachable);
+        assert!(!status.relays[1].reachable);
+        assert!(status.relays[1].error.is_some());
+    }
+}
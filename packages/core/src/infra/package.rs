@@ -1,26 +1,43 @@
-use crate::domain::package::{Package, PackageDefinition};
-use crate::infra::docker::{
-    create_or_recreate_network, find_container, get_docker_instance, pull_and_start_container,
-    remove_container,
-};
-use crate::manifests::ethereum::Ethereum;
-use eyre::Result;
+use crate::domain::container::Container;
+use crate::domain::image::{ImageLock, InstalledImage};
+use crate::domain::package::{Package, PackageConfig};
+use crate::domain::package_status::{ContainerState, PackageStatus};
+use crate::domain::ports::PortConflictError;
+use crate::infra::install_plan::InstallPlan;
+use crate::infra::manifest::{ManifestStore, default_config, render_package};
+use crate::infra::ports::check_port_conflicts;
+use crate::infra::pull_progress::OnPullProgress;
+use crate::infra::runtime::ContainerRuntime;
+use eyre::{Result, bail};
 use std::{
     collections::{HashMap, HashSet},
     fs,
+    path::Path,
+    time::Duration,
 };
-use tracing::info;
+use tracing::{error, info, warn};
 
-/// Retrieves a `HashMap` of all available packages.
+/// Retrieves a `HashMap` of all available packages, rendered with their default config.
 pub fn get_packages() -> Result<HashMap<String, Package>> {
     let mut packages = HashMap::new();
-    packages.insert(Ethereum::NAME.to_string(), Ethereum::get_package()?);
+    for manifest in ManifestStore::load_all()? {
+        let package = render_package(&manifest, &default_config(&manifest))?;
+        packages.insert(package.name.clone(), package);
+    }
     Ok(packages)
 }
 
+/// Retrieves a single package, rendered with the given config.
+pub fn get_package(name: &str, config: &PackageConfig) -> Result<Package> {
+    let manifest = ManifestStore::load(name)?;
+    render_package(&manifest, config)
+}
+
 /// Gets a list of installed packages by checking their container states
-pub async fn get_installed_packages(packages: &HashMap<String, Package>) -> Result<Vec<Package>> {
-    let docker = get_docker_instance()?;
+pub(crate) async fn get_installed_packages(
+    runtime: &impl ContainerRuntime,
+    packages: &HashMap<String, Package>,
+) -> Result<Vec<Package>> {
     let mut installed = Vec::new();
 
     for package in packages.values() {
@@ -28,7 +45,7 @@ pub async fn get_installed_packages(packages: &HashMap<String, Package>) -> Resu
 
         for container in &package.containers {
             info!("Checking container '{}'...", container.name);
-            if find_container(&docker, &container.name).await?.is_empty() {
+            if !runtime.container_exists(&container.name).await? {
                 all_containers_exist = false;
                 break;
             }
@@ -42,30 +59,276 @@ pub async fn get_installed_packages(packages: &HashMap<String, Package>) -> Resu
     Ok(installed)
 }
 
-/// Installs a package with the given network configuration
-pub async fn install_package(package: &Package, network: Option<&str>) -> Result<()> {
-    let docker = get_docker_instance()?;
-    let containers = match package.name.as_str() {
-        "Ethereum" => Ethereum::get_containers(network.unwrap_or("holesky"))?,
-        _ => package.containers.clone(),
-    };
+/// Gets the status of every container of a package
+pub(crate) async fn get_package_status(
+    runtime: &impl ContainerRuntime,
+    package: &Package,
+) -> Result<PackageStatus> {
+    let mut containers = Vec::new();
+    for container in &package.containers {
+        containers.push(runtime.container_status(&container.name).await?);
+    }
+    Ok(PackageStatus::from_containers(&package.name, containers))
+}
 
-    info!("Creating network '{}'...", package.network_name);
-    create_or_recreate_network(&docker, &package.network_name).await?;
+/// Installs a package whose containers have been rendered from its config, returning the
+/// images the containers were created from. Fails before changing anything if a host port is
+/// taken, and rolls back a failed install.
+pub(crate) async fn install_package(
+    runtime: &impl ContainerRuntime,
+    package: &Package,
+    kittynode_path: &Path,
+    on_progress: OnPullProgress<'_>,
+) -> Result<ImageLock> {
+    let conflicts = check_port_conflicts(runtime, package).await?;
+    if !conflicts.is_empty() {
+        return Err(PortConflictError {
+            package: package.name.clone(),
+            conflicts,
+        }
+        .into());
+    }
+
+    InstallPlan::new(package, kittynode_path)
+        .execute(runtime, on_progress)
+        .await
+}
+
+/// Starts the containers of an installed package in the order they are declared
+pub(crate) async fn start_package(
+    runtime: &impl ContainerRuntime,
+    package: &Package,
+) -> Result<()> {
+    ensure_containers_exist(runtime, package).await?;
 
-    for container in &containers {
+    for container in &package.containers {
+        if runtime.is_container_running(&container.name).await? {
+            info!("Container '{}' is already running", container.name);
+            continue;
+        }
         info!("Starting container '{}'...", container.name);
-        pull_and_start_container(&docker, container, &package.network_name).await?;
+        runtime.start_container(&container.name).await?;
         info!("Container '{}' started successfully", container.name);
     }
 
     Ok(())
 }
 
-/// Deletes a package and its associated resources
-pub async fn delete_package(package: &Package, include_images: bool) -> Result<()> {
-    let docker = get_docker_instance()?;
+/// Stops the containers of an installed package in reverse order, keeping containers and data
+pub(crate) async fn stop_package(runtime: &impl ContainerRuntime, package: &Package) -> Result<()> {
+    ensure_containers_exist(runtime, package).await?;
+
+    for container in package.containers.iter().rev() {
+        if !runtime.is_container_running(&container.name).await? {
+            info!("Container '{}' is already stopped", container.name);
+            continue;
+        }
+        info!("Stopping container '{}'...", container.name);
+        runtime
+            .stop_container(&container.name, container.stop_timeout_secs)
+            .await?;
+        info!("Container '{}' stopped successfully", container.name);
+    }
+
+    Ok(())
+}
+
+/// Stops and then starts the containers of an installed package
+pub(crate) async fn restart_package(
+    runtime: &impl ContainerRuntime,
+    package: &Package,
+) -> Result<()> {
+    stop_package(runtime, package).await?;
+    start_package(runtime, package).await
+}
+
+async fn ensure_containers_exist(runtime: &impl ContainerRuntime, package: &Package) -> Result<()> {
+    for container in &package.containers {
+        if !runtime.container_exists(&container.name).await? {
+            bail!(
+                "Package '{}' is not installed, container '{}' does not exist",
+                package.name,
+                container.name
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Upgrades an installed package to the images its containers now resolve to.
+///
+/// New images are pulled before anything is touched. Containers are then recreated one at a
+/// time, keeping their volumes and bind mounts, and each must still be running after
+/// `health_check_grace`. If any container fails, every container recreated so far is rolled
+/// back to the image recorded in `previous`.
+pub(crate) async fn upgrade_package(
+    runtime: &impl ContainerRuntime,
+    package: &Package,
+    previous: &ImageLock,
+    health_check_grace: Duration,
+    on_progress: OnPullProgress<'_>,
+) -> Result<ImageLock> {
+    for container in &package.containers {
+        if !previous.containers.contains_key(&container.name) {
+            bail!(
+                "No image was recorded for container '{}', so it cannot be rolled back",
+                container.name
+            );
+        }
+    }
 
+    // Pull every new image before any container is touched
+    let mut lock = ImageLock::default();
+    for container in &package.containers {
+        let image = pull_image(runtime, container, on_progress).await?;
+        lock.containers.insert(container.name.clone(), image);
+    }
+
+    let mut recreated = Vec::new();
+    for container in &package.containers {
+        let new_image = &lock.containers[&container.name];
+        let old_image = &previous.containers[&container.name];
+        if new_image == old_image {
+            info!("Container '{}' is already up to date", container.name);
+            continue;
+        }
+
+        info!(
+            "Upgrading container '{}' from {} to {}...",
+            container.name, old_image, new_image
+        );
+        recreated.push(container);
+        let result = recreate_container(runtime, container, &package.network_name).await;
+        let result = match result {
+            Ok(()) => check_container_health(runtime, &container.name, health_check_grace).await,
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            error!("Upgrade of container '{}' failed: {}", container.name, e);
+            let rollback = rollback_containers(
+                runtime,
+                &recreated,
+                previous,
+                &package.network_name,
+                on_progress,
+            )
+            .await;
+            return Err(match rollback {
+                Ok(()) => e.wrap_err(format!(
+                    "Failed to upgrade container '{}', the package was rolled back",
+                    container.name
+                )),
+                Err(rollback_error) => e.wrap_err(format!(
+                    "Failed to upgrade container '{}' and the rollback failed: {:#}",
+                    container.name, rollback_error
+                )),
+            });
+        }
+        info!("Container '{}' upgraded successfully", container.name);
+    }
+
+    Ok(lock)
+}
+
+/// Recreates containers from the images recorded before an upgrade.
+async fn rollback_containers(
+    runtime: &impl ContainerRuntime,
+    containers: &[&Container],
+    previous: &ImageLock,
+    network_name: &str,
+    on_progress: OnPullProgress<'_>,
+) -> Result<()> {
+    for container in containers.iter().rev() {
+        let image = &previous.containers[&container.name];
+        warn!("Rolling back container '{}' to {}", container.name, image);
+
+        let mut previous_container = (*container).clone();
+        previous_container.tag = image.tag.clone();
+        previous_container.digest = image.digest.clone();
+
+        runtime
+            .pull_image(&previous_container.image_reference(), on_progress)
+            .await?;
+        recreate_container(runtime, &previous_container, network_name).await?;
+    }
+    Ok(())
+}
+
+/// Replaces a container with a new one created from the given definition.
+async fn recreate_container(
+    runtime: &impl ContainerRuntime,
+    container: &Container,
+    network_name: &str,
+) -> Result<()> {
+    runtime
+        .remove_container(&container.name, container.stop_timeout_secs)
+        .await?;
+    runtime.create_container(container).await?;
+    runtime.start_container(&container.name).await?;
+    runtime
+        .connect_network(network_name, &container.name)
+        .await?;
+    Ok(())
+}
+
+/// Fails unless the container is still running once the grace period has passed, without
+/// having been restarted by its restart policy.
+async fn check_container_health(
+    runtime: &impl ContainerRuntime,
+    name: &str,
+    grace: Duration,
+) -> Result<()> {
+    tokio::time::sleep(grace).await;
+    let status = runtime.container_status(name).await?;
+    if status.state != ContainerState::Running {
+        bail!("Container '{}' is {} after {:?}", name, status.state, grace);
+    }
+    // The container was just created, so any restart means it crashed
+    if status.restart_count > 0 {
+        bail!(
+            "Container '{}' crashed and was restarted {} times within {:?}",
+            name,
+            status.restart_count,
+            grace
+        );
+    }
+    Ok(())
+}
+
+/// Reinstalls a package with a new config, keeping its volumes and bind mount data.
+///
+/// Only the containers and network of `previous` are removed, so a config change such as a
+/// remapped port does not cost the chain data the clients have synced.
+pub(crate) async fn reconfigure_package(
+    runtime: &impl ContainerRuntime,
+    previous: &Package,
+    package: &Package,
+    kittynode_path: &Path,
+    on_progress: OnPullProgress<'_>,
+) -> Result<ImageLock> {
+    for container in previous.containers.iter().rev() {
+        info!("Removing container '{}'...", container.name);
+        runtime
+            .remove_container(&container.name, container.stop_timeout_secs)
+            .await?;
+    }
+    info!("Removing network '{}'...", previous.network_name);
+    runtime.remove_network(&previous.network_name).await?;
+
+    install_package(runtime, package, kittynode_path, on_progress).await
+}
+
+/// Deletes a package and its associated resources.
+///
+/// Only bind mount sources under `kittynode_path` that are not marked to persist are removed,
+/// since others belong to the user.
+pub(crate) async fn delete_package(
+    runtime: &impl ContainerRuntime,
+    package: &Package,
+    kittynode_path: &Path,
+    include_images: bool,
+) -> Result<()> {
     // Clean up containers and collect resources to remove
     let mut image_names = Vec::new();
     let mut file_paths = HashSet::new();
@@ -74,12 +337,15 @@ pub async fn delete_package(package: &Package, include_images: bool) -> Result<(
 
     for container in &package.containers {
         if include_images {
-            image_names.push(&container.image);
+            image_names.push(container.image_reference());
         }
 
         volume_names.extend(container.volume_bindings.iter().map(|b| &b.source));
 
         for binding in &container.file_bindings {
+            if binding.persist || !Path::new(&binding.source).starts_with(kittynode_path) {
+                continue;
+            }
             if let Ok(metadata) = fs::metadata(&binding.source) {
                 if metadata.is_dir() {
                     directory_paths.insert(&binding.source);
@@ -90,14 +356,16 @@ pub async fn delete_package(package: &Package, include_images: bool) -> Result<(
         }
 
         info!("Removing container '{}'...", container.name);
-        remove_container(&docker, &container.name).await?;
+        runtime
+            .remove_container(&container.name, container.stop_timeout_secs)
+            .await?;
         info!("Container '{}' removed successfully", container.name);
     }
 
     // Clean up images if requested
     for image in image_names {
         info!("Removing image '{}'...", image);
-        docker.remove_image(image, None, None).await?;
+        runtime.remove_image(&image).await?;
         info!("Image '{}' removed successfully", image);
     }
 
@@ -116,13 +384,390 @@ pub async fn delete_package(package: &Package, include_images: bool) -> Result<(
     // Clean up Docker volumes and network
     for volume in volume_names {
         info!("Removing volume '{}'...", volume);
-        docker.remove_volume(volume, None).await?;
+        runtime.remove_volume(volume).await?;
         info!("Volume '{}' removed successfully", volume);
     }
 
     info!("Removing network '{}'...", package.network_name);
-    docker.remove_network(&package.network_name).await?;
+    runtime.remove_network(&package.network_name).await?;
     info!("Network '{}' removed successfully", package.network_name);
 
     Ok(())
 }
+
+/// Pulls the image of a container and resolves the digest it was pulled at.
+pub(crate) async fn pull_image(
+    runtime: &impl ContainerRuntime,
+    container: &Container,
+    on_progress: OnPullProgress<'_>,
+) -> Result<InstalledImage> {
+    let reference = container.image_reference();
+    info!("Pulling image '{}'...", reference);
+    runtime.pull_image(&reference, on_progress).await?;
+
+    let digest = match &container.digest {
+        Some(digest) => Some(digest.clone()),
+        None => runtime.image_digest(&reference).await?,
+    };
+    info!(
+        "Pulled image '{}' at digest {}",
+        reference,
+        digest.as_deref().unwrap_or("unknown")
+    );
+
+    Ok(InstalledImage {
+        image: container.image.clone(),
+        tag: container.tag.clone(),
+        digest,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::image::PullProgress;
+    use crate::domain::package_status::PackageState;
+    use crate::infra::fake_runtime::FakeRuntime;
+    use crate::infra::manifest::{parse_manifest, render_package_with_root};
+    use tempfile::tempdir;
+
+    fn ethereum_package(root: &std::path::Path) -> Package {
+        let manifest = parse_manifest(include_str!("../manifests/ethereum.toml")).unwrap();
+        render_package_with_root(&manifest, &default_config(&manifest), root).unwrap()
+    }
+
+    #[tokio::test]
+    async fn installs_and_deletes_a_package() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let packages = HashMap::from([(package.name.clone(), package.clone())]);
+        let runtime = FakeRuntime::new();
+
+        let lock = install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+        assert_eq!(
+            lock.containers["reth-node"].tag,
+            package.default_config.values["reth_version"]
+        );
+        assert!(lock.containers["reth-node"].digest.is_some());
+        {
+            let state = runtime.state();
+            assert!(state.networks.contains("ethereum-network"));
+            assert!(state.volumes.contains("rethdata"));
+            for container in &package.containers {
+                let fake = &state.containers[&container.name];
+                assert_eq!(fake.container.image, container.image);
+                assert!(fake.running, "{} is not running", container.name);
+                assert!(fake.networks.contains("ethereum-network"));
+            }
+        }
+        let installed = get_installed_packages(&runtime, &packages).await.unwrap();
+        assert_eq!(installed.len(), 1);
+
+        delete_package(&runtime, &package, temp_dir.path(), true)
+            .await
+            .unwrap();
+        {
+            let state = runtime.state();
+            assert!(state.networks.is_empty());
+            assert!(state.containers.is_empty());
+            assert!(state.volumes.is_empty());
+            assert!(state.images.is_empty());
+        }
+        let installed = get_installed_packages(&runtime, &packages).await.unwrap();
+        assert!(installed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn stops_and_starts_a_package() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        assert!(start_package(&runtime, &package).await.is_err());
+
+        install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+        stop_package(&runtime, &package).await.unwrap();
+        for container in &package.containers {
+            assert!(!runtime.state().containers[&container.name].running);
+        }
+        assert_eq!(runtime.state().stop_timeouts["reth-node"], Some(300));
+
+        let status = get_package_status(&runtime, &package).await.unwrap();
+        assert_eq!(status.state, PackageState::Stopped);
+
+        restart_package(&runtime, &package).await.unwrap();
+        let status = get_package_status(&runtime, &package).await.unwrap();
+        assert_eq!(status.state, PackageState::Running);
+        assert!(runtime.state().volumes.contains("rethdata"));
+    }
+
+    #[tokio::test]
+    async fn delete_fails_when_a_container_does_not_stop() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+
+        runtime.fail_on("stop_container", "lighthouse-node");
+        let error = delete_package(&runtime, &package, temp_dir.path(), false)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("lighthouse-node"));
+        assert!(runtime.state().containers.contains_key("lighthouse-node"));
+    }
+
+    #[tokio::test]
+    async fn upgrades_changed_containers() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        let previous = install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+
+        let mut upgraded = package.clone();
+        upgraded.containers[0].tag = "v9.9.9".to_string();
+        let lock = upgrade_package(&runtime, &upgraded, &previous, Duration::ZERO, &|_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(lock.containers["reth-node"].tag, "v9.9.9");
+        assert_eq!(
+            lock.containers["lighthouse-node"],
+            previous.containers["lighthouse-node"]
+        );
+        let state = runtime.state();
+        let reth = &state.containers["reth-node"];
+        assert!(reth.running);
+        assert_eq!(reth.container.tag, "v9.9.9");
+        assert!(state.volumes.contains("rethdata"));
+    }
+
+    #[tokio::test]
+    async fn rolls_back_when_an_upgraded_container_crashes() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        let previous = install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+
+        let mut upgraded = package.clone();
+        upgraded.containers[0].tag = "v9.9.9".to_string();
+        upgraded.containers[1].tag = "v9.9.9".to_string();
+        runtime
+            .state()
+            .crashing_images
+            .insert(upgraded.containers[1].image_reference());
+
+        let result = upgrade_package(&runtime, &upgraded, &previous, Duration::ZERO, &|_| {}).await;
+        assert!(result.is_err());
+
+        let state = runtime.state();
+        for container in &package.containers {
+            let fake = &state.containers[&container.name];
+            let image = &previous.containers[&container.name];
+            assert!(fake.running, "{} is not running", container.name);
+            assert_eq!(fake.container.tag, image.tag);
+            assert_eq!(fake.container.digest, image.digest);
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_the_upgrade_error_when_the_rollback_fails() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        let previous = install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+
+        let mut upgraded = package.clone();
+        upgraded.containers[0].tag = "v9.9.9".to_string();
+        runtime
+            .state()
+            .crashing_images
+            .insert(upgraded.containers[0].image_reference());
+        let mut rolled_back = package.containers[0].clone();
+        let image = &previous.containers[&rolled_back.name];
+        rolled_back.tag = image.tag.clone();
+        rolled_back.digest = image.digest.clone();
+        runtime.fail_on("pull_image", &rolled_back.image_reference());
+
+        let error = upgrade_package(&runtime, &upgraded, &previous, Duration::ZERO, &|_| {})
+            .await
+            .unwrap_err();
+        let message = format!("{error:#}");
+        assert!(message.contains("the rollback failed"), "{message}");
+        assert!(message.contains("exited"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn reconfigure_keeps_volumes_and_bind_data() {
+        let temp_dir = tempdir().unwrap();
+        let manifest = parse_manifest(include_str!("../manifests/ethereum.toml")).unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+        let data_dir = package
+            .containers
+            .iter()
+            .flat_map(|container| &container.file_bindings)
+            .map(|binding| Path::new(&binding.source))
+            .find(|source| source.is_dir() && source.starts_with(temp_dir.path()))
+            .unwrap();
+        fs::write(data_dir.join("chain.db"), "synced").unwrap();
+
+        let mut config = default_config(&manifest);
+        config
+            .values
+            .insert("execution_rpc_port".to_string(), "18545".to_string());
+        let reconfigured = render_package_with_root(&manifest, &config, temp_dir.path()).unwrap();
+        reconfigure_package(&runtime, &package, &reconfigured, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+
+        assert!(data_dir.join("chain.db").exists());
+        let state = runtime.state();
+        assert!(state.volumes.contains("rethdata"));
+        let reth = &state.containers["reth-node"];
+        assert!(reth.running);
+        assert!(
+            reth.container
+                .port_bindings
+                .values()
+                .flatten()
+                .any(|binding| binding.host_port.as_deref() == Some("18545"))
+        );
+    }
+
+    #[tokio::test]
+    async fn rolls_back_when_an_upgraded_container_keeps_restarting() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        let previous = install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+
+        let mut upgraded = package.clone();
+        upgraded.containers[0].tag = "v9.9.9".to_string();
+        runtime
+            .state()
+            .restarting_images
+            .insert(upgraded.containers[0].image_reference());
+
+        let result = upgrade_package(&runtime, &upgraded, &previous, Duration::ZERO, &|_| {}).await;
+        assert!(result.is_err());
+        let state = runtime.state();
+        let reth = &state.containers["reth-node"];
+        assert!(reth.running);
+        assert_eq!(reth.container.tag, previous.containers["reth-node"].tag);
+    }
+
+    #[tokio::test]
+    async fn install_recreates_an_existing_network() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        runtime.create_network("ethereum-network").await.unwrap();
+
+        install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+        assert!(runtime.state().networks.contains("ethereum-network"));
+    }
+
+    #[tokio::test]
+    async fn install_fails_when_a_container_fails_to_start() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        runtime.fail_on("start_container", "lighthouse-node");
+
+        let error = install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("start container 'lighthouse-node'"),
+            "{error}"
+        );
+
+        let state = runtime.state();
+        assert!(state.containers.is_empty());
+        assert!(state.networks.is_empty());
+        assert!(!temp_dir.path().join("jwt.hex").exists());
+        assert!(!temp_dir.path().join(".lighthouse").exists());
+    }
+
+    #[tokio::test]
+    async fn install_reports_pull_progress_and_fails_on_pull_errors() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+
+        let pulled = std::sync::Mutex::new(Vec::new());
+        let on_progress = |progress: &PullProgress| {
+            if progress.done {
+                pulled.lock().unwrap().push(progress.reference.clone());
+            }
+        };
+        install_package(&runtime, &package, temp_dir.path(), &on_progress)
+            .await
+            .unwrap();
+        let references: Vec<_> = package
+            .containers
+            .iter()
+            .map(Container::image_reference)
+            .collect();
+        assert_eq!(*pulled.lock().unwrap(), references);
+
+        let runtime = FakeRuntime::new();
+        runtime.fail_on("pull_image", &references[1]);
+        assert!(
+            install_package(&runtime, &package, temp_dir.path(), &|_| {})
+                .await
+                .is_err()
+        );
+        assert!(!runtime.state().containers.contains_key("lighthouse-node"));
+    }
+
+    #[tokio::test]
+    async fn install_fails_on_port_conflicts() {
+        let temp_dir = tempdir().unwrap();
+        let package = ethereum_package(temp_dir.path());
+        let runtime = FakeRuntime::new();
+        runtime.state().host_listeners.insert(30303);
+
+        let mut other = package.containers[1].clone();
+        other.name = "other-beacon-node".to_string();
+        runtime
+            .pull_image(&other.image_reference(), &|_| {})
+            .await
+            .unwrap();
+        runtime.create_container(&other).await.unwrap();
+        runtime.start_container(&other.name).await.unwrap();
+
+        let error = install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap_err();
+        let error = error.downcast_ref::<PortConflictError>().unwrap();
+        assert!(
+            error
+                .conflicts
+                .iter()
+                .any(|c| c.host_port == 30303 && c.container == "reth-node")
+        );
+        assert!(error.conflicts.iter().any(|c| c.host_port == 5052));
+        assert!(!runtime.state().containers.contains_key("reth-node"));
+    }
+}
@@ -1,12 +1,24 @@
 use crate::domain::package::PackageConfig;
 use crate::infra::file::kittynode_path;
+use crate::infra::manifest::ManifestStore;
 use eyre::Result;
 use std::{fs, path::PathBuf};
 
 pub struct PackageConfigStore;
 
 impl PackageConfigStore {
+    /// Loads the saved config of a package, checking it against the package config schema.
     pub fn load(package_name: &str) -> Result<PackageConfig> {
+        let config = Self::read(package_name)?;
+        if !config.values.is_empty() {
+            let schema = ManifestStore::load(package_name)?.config_schema();
+            schema.validate(package_name, &config)?;
+        }
+        Ok(config)
+    }
+
+    /// Loads the saved config of a package without checking it.
+    pub(crate) fn read(package_name: &str) -> Result<PackageConfig> {
         let config_path = Self::config_file_path(package_name)?;
         if !config_path.exists() {
             return Ok(PackageConfig::default());
@@ -0,0 +1,225 @@
+use crate::domain::package::Package;
+use crate::domain::ports::{PortConflict, PortConflictSource, PublishedPort};
+use crate::infra::runtime::ContainerRuntime;
+use eyre::{Result, eyre};
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, TcpListener, UdpSocket};
+
+/// Returns the host ports the containers of a package publish.
+pub(crate) fn package_ports(package: &Package) -> Result<Vec<PublishedPort>> {
+    let mut ports = Vec::new();
+    for container in &package.containers {
+        for (container_port, bindings) in &container.port_bindings {
+            let (_, protocol) = container_port.split_once('/').ok_or_else(|| {
+                eyre!(
+                    "Container '{}' has invalid port '{}'",
+                    container.name,
+                    container_port
+                )
+            })?;
+            for binding in bindings {
+                let host_port = binding.host_port.as_deref().unwrap_or_default();
+                ports.push(PublishedPort {
+                    container: container.name.clone(),
+                    host_ip: binding.host_ip.clone().unwrap_or_default(),
+                    host_port: host_port.parse().map_err(|_| {
+                        eyre!(
+                            "Container '{}' has invalid host port '{}'",
+                            container.name,
+                            host_port
+                        )
+                    })?,
+                    protocol: protocol.to_string(),
+                });
+            }
+        }
+    }
+    ports.sort_by(|a, b| (&a.container, a.host_port).cmp(&(&b.container, b.host_port)));
+    Ok(ports)
+}
+
+/// Finds the host ports of a package that are already taken, whether by another container of the
+/// package, by a container outside the package or by a host process.
+pub(crate) async fn check_port_conflicts(
+    runtime: &impl ContainerRuntime,
+    package: &Package,
+) -> Result<Vec<PortConflict>> {
+    let ports = package_ports(package)?;
+    let (own, published): (Vec<_>, Vec<_>) = runtime
+        .published_ports()
+        .await?
+        .into_iter()
+        .partition(|port| package.containers.iter().any(|c| c.name == port.container));
+    // The runtime itself holds the ports the package already publishes, so probing them would
+    // report an installed package as conflicting with itself
+    Ok(find_port_conflicts(&ports, &published, |port| {
+        own.iter().any(|other| port.overlaps(other)) || runtime.is_host_port_free(port)
+    }))
+}
+
+fn find_port_conflicts(
+    ports: &[PublishedPort],
+    published: &[PublishedPort],
+    is_free: impl Fn(&PublishedPort) -> bool,
+) -> Vec<PortConflict> {
+    let mut conflicts = Vec::new();
+    for (i, port) in ports.iter().enumerate() {
+        let source = if let Some(earlier) = ports[..i].iter().find(|other| port.overlaps(other)) {
+            PortConflictSource::Package {
+                container: earlier.container.clone(),
+            }
+        } else if let Some(other) = published.iter().find(|other| port.overlaps(other)) {
+            PortConflictSource::Container {
+                name: other.container.clone(),
+            }
+        } else if !is_free(port) {
+            PortConflictSource::HostListener
+        } else {
+            continue;
+        };
+
+        conflicts.push(PortConflict {
+            container: port.container.clone(),
+            host_ip: port.host_ip.clone(),
+            host_port: port.host_port,
+            protocol: port.protocol.clone(),
+            source,
+        });
+    }
+    conflicts
+}
+
+/// Probes whether a host port can be bound. Only an address in use counts as taken, so ports
+/// the current user may not bind, such as privileged ports, are reported as free.
+pub(crate) fn is_host_port_free(port: &PublishedPort) -> bool {
+    let ip = port
+        .host_ip
+        .parse()
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    let result = match port.protocol.as_str() {
+        "udp" => UdpSocket::bind((ip, port.host_port)).map(drop),
+        _ => TcpListener::bind((ip, port.host_port)).map(drop),
+    };
+    !matches!(result, Err(e) if e.kind() == ErrorKind::AddrInUse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ports::WEB_SERVER_PORT;
+    use crate::infra::fake_runtime::FakeRuntime;
+    use crate::infra::manifest::{default_config, parse_manifest, render_package_with_root};
+    use crate::infra::package::install_package;
+    use crate::manifests::BUILTIN_MANIFESTS;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn port(container: &str, host_ip: &str, host_port: u16, protocol: &str) -> PublishedPort {
+        PublishedPort {
+            container: container.to_string(),
+            host_ip: host_ip.to_string(),
+            host_port,
+            protocol: protocol.to_string(),
+        }
+    }
+
+    #[test]
+    fn finds_conflicts_by_source() {
+        let ports = [
+            port("reth-node", "0.0.0.0", 9001, "tcp"),
+            port("lighthouse-node", "0.0.0.0", 9001, "udp"),
+            port("lighthouse-node", "127.0.0.1", 5052, "tcp"),
+            port("lighthouse-node", "0.0.0.0", 9000, "tcp"),
+            port("validator", "0.0.0.0", 9000, "tcp"),
+            port("lighthouse-node", "0.0.0.0", 30303, "tcp"),
+        ];
+        let published = [
+            port("grafana", "0.0.0.0", 5052, "tcp"),
+            port("other", "127.0.0.2", 9001, "tcp"),
+        ];
+
+        let conflicts = find_port_conflicts(&ports, &published, |port| port.host_port != 30303);
+        let sources: Vec<_> = conflicts
+            .iter()
+            .map(|c| (c.host_port, c.source.clone()))
+            .collect();
+        assert_eq!(
+            sources,
+            [
+                (
+                    9001,
+                    PortConflictSource::Container {
+                        name: "other".to_string()
+                    }
+                ),
+                (
+                    5052,
+                    PortConflictSource::Container {
+                        name: "grafana".to_string()
+                    }
+                ),
+                (
+                    9000,
+                    PortConflictSource::Package {
+                        container: "lighthouse-node".to_string()
+                    }
+                ),
+                (30303, PortConflictSource::HostListener),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_host_listeners() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let taken = port(
+            "reth-node",
+            "127.0.0.1",
+            listener.local_addr().unwrap().port(),
+            "tcp",
+        );
+        assert!(!is_host_port_free(&taken));
+
+        drop(listener);
+        assert!(is_host_port_free(&taken));
+    }
+
+    #[test]
+    fn builtin_defaults_leave_the_web_server_port_free() {
+        for (file_name, contents) in BUILTIN_MANIFESTS {
+            let manifest = parse_manifest(contents).unwrap();
+            let package =
+                render_package_with_root(&manifest, &default_config(&manifest), Path::new("/k"))
+                    .unwrap();
+            for port in package_ports(&package).unwrap() {
+                assert_ne!(
+                    port.host_port, WEB_SERVER_PORT,
+                    "{file_name} publishes the web server port for {}",
+                    port.container
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn an_installed_package_does_not_conflict_with_itself() {
+        let temp_dir = tempdir().unwrap();
+        let manifest = parse_manifest(include_str!("../manifests/ethereum.toml")).unwrap();
+        let package =
+            render_package_with_root(&manifest, &default_config(&manifest), temp_dir.path())
+                .unwrap();
+        let runtime = FakeRuntime::new();
+        install_package(&runtime, &package, temp_dir.path(), &|_| {})
+            .await
+            .unwrap();
+        // The runtime listens on the published ports on behalf of the containers
+        let ports = package_ports(&package).unwrap();
+        runtime
+            .state()
+            .host_listeners
+            .extend(ports.iter().map(|port| port.host_port));
+
+        let conflicts = check_port_conflicts(&runtime, &package).await.unwrap();
+        assert!(conflicts.is_empty(), "{conflicts:?}");
+    }
+}
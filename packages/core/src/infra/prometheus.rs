@@ -0,0 +1,174 @@
+use crate::domain::metrics::{JobMetrics, Selector};
+use crate::domain::package::{MetricsEndpoint, Package};
+use eyre::{Result, bail, eyre};
+use reqwest::Client;
+
+/// A sample of the Prometheus text exposition format.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Sample {
+    pub(crate) name: String,
+    pub(crate) labels: Vec<(String, String)>,
+    pub(crate) value: f64,
+}
+
+/// Scrapes the key series of every metrics endpoint of a package that is reachable from the
+/// host. An endpoint that cannot be scraped is reported with an error.
+pub(crate) async fn scrape_package(client: &Client, package: &Package) -> Vec<JobMetrics> {
+    let mut jobs = Vec::new();
+    for endpoint in &package.metrics {
+        let Some(host_url) = &endpoint.host_url else {
+            continue;
+        };
+        let job = match scrape(client, host_url, &endpoint.path).await {
+            Ok(samples) => read_series(endpoint, &samples),
+            Err(e) => JobMetrics {
+                job: endpoint.job.clone(),
+                error: Some(format!("{e:#}")),
+                ..Default::default()
+            },
+        };
+        jobs.push(job);
+    }
+    jobs
+}
+
+async fn scrape(client: &Client, host_url: &str, path: &str) -> Result<Vec<Sample>> {
+    let url = format!("{}{}", host_url.trim_end_matches('/'), path);
+    let body = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    parse_text(&body)
+}
+
+fn read_series(endpoint: &MetricsEndpoint, samples: &[Sample]) -> JobMetrics {
+    let read = |selector: &Option<String>| {
+        selector
+            .as_deref()
+            .and_then(|selector| selector.parse::<Selector>().ok())
+            .and_then(|selector| sum(samples, &selector))
+    };
+    JobMetrics {
+        job: endpoint.job.clone(),
+        sync_height: read(&endpoint.series.sync_height),
+        peers: read(&endpoint.series.peers),
+        cpu_seconds: read(&endpoint.series.cpu_seconds),
+        cpu_percent: None,
+        disk_bytes: read(&endpoint.series.disk_bytes),
+        error: None,
+    }
+}
+
+/// Returns the sum of the samples a selector matches, or `None` if it matches none.
+pub(crate) fn sum(samples: &[Sample], selector: &Selector) -> Option<f64> {
+    samples
+        .iter()
+        .filter(|sample| selector.matches(&sample.name, &sample.labels))
+        .map(|sample| sample.value)
+        .reduce(|a, b| a + b)
+}
+
+/// Parses the Prometheus text exposition format, skipping comments and timestamps.
+pub(crate) fn parse_text(text: &str) -> Result<Vec<Sample>> {
+    let mut samples = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        samples.push(parse_line(line).ok_or_else(|| eyre!("Invalid sample '{}'", line))?);
+    }
+    Ok(samples)
+}
+
+fn parse_line(line: &str) -> Option<Sample> {
+    let name_end = line.find(|c: char| c == '{' || c.is_whitespace())?;
+    let name = &line[..name_end];
+    let mut rest = &line[name_end..];
+
+    let mut labels = Vec::new();
+    if let Some(label_text) = rest.strip_prefix('{') {
+        let (parsed, remaining) = parse_labels(label_text).ok()?;
+        labels = parsed;
+        rest = remaining;
+    }
+
+    let value = rest.split_whitespace().next()?.parse().ok()?;
+    Some(Sample {
+        name: name.to_string(),
+        labels,
+        value,
+    })
+}
+
+/// Parses `label="value",...}`, returning the labels and the text after the closing brace.
+fn parse_labels(text: &str) -> Result<(Vec<(String, String)>, &str)> {
+    let mut labels = Vec::new();
+    let mut rest = text.trim_start();
+    loop {
+        if let Some(remaining) = rest.strip_prefix('}') {
+            return Ok((labels, remaining));
+        }
+        let Some((label, value_text)) = rest.split_once('=') else {
+            bail!("Expected a label");
+        };
+        let Some(value_text) = value_text.strip_prefix('"') else {
+            bail!("Expected a quoted label value");
+        };
+
+        let mut value = String::new();
+        let mut chars = value_text.char_indices();
+        let end = loop {
+            match chars.next() {
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, c)) => value.push(c),
+                    None => bail!("Unterminated label value"),
+                },
+                Some((i, '"')) => break i,
+                Some((_, c)) => value.push(c),
+                None => bail!("Unterminated label value"),
+            }
+        };
+        labels.push((
+            label.trim().trim_start_matches(',').trim().to_string(),
+            value,
+        ));
+        rest = value_text[end + 1..].trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_text_format() {
+        let text = r#"
+# HELP reth_sync_checkpoint The sync checkpoint of each stage
+# TYPE reth_sync_checkpoint gauge
+reth_sync_checkpoint{stage="Headers"} 2000
+reth_sync_checkpoint{stage="Finish"} 1500
+reth_network_connected_peers 12
+process_cpu_seconds_total 42.5 1700000000000
+store_disk_db_size{path="C:\\data",note="a \"quoted\", value"} 1e9
+"#;
+        let samples = parse_text(text).unwrap();
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples[4].labels[0].1, r"C:\data");
+        assert_eq!(samples[4].labels[1].1, r#"a "quoted", value"#);
+        assert_eq!(samples[4].value, 1e9);
+
+        let selector = r#"reth_sync_checkpoint{stage="Finish"}"#.parse().unwrap();
+        assert_eq!(sum(&samples, &selector), Some(1500.0));
+        let selector = "reth_sync_checkpoint".parse().unwrap();
+        assert_eq!(sum(&samples, &selector), Some(3500.0));
+        let selector = "libp2p_peers".parse().unwrap();
+        assert_eq!(sum(&samples, &selector), None);
+
+        assert!(parse_text("reth_sync_checkpoint{stage=\"Finish} 1").is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use crate::domain::image::PullProgress;
+use std::collections::HashMap;
+
+/// A callback that receives progress updates while images are pulled.
+pub(crate) type OnPullProgress<'a> = &'a (dyn Fn(&PullProgress) + Sync);
+
+#[derive(Default)]
+struct LayerProgress {
+    downloaded: u64,
+    total: u64,
+    done: bool,
+}
+
+/// Aggregates the per-layer status messages of an image pull into a single progress value.
+pub(crate) struct PullTracker {
+    reference: String,
+    layers: HashMap<String, LayerProgress>,
+}
+
+impl PullTracker {
+    pub(crate) fn new(reference: &str) -> Self {
+        Self {
+            reference: reference.to_string(),
+            layers: HashMap::new(),
+        }
+    }
+
+    /// Records a status message for a layer, as reported by the engine.
+    pub(crate) fn update(
+        &mut self,
+        layer: &str,
+        status: &str,
+        current: Option<u64>,
+        total: Option<u64>,
+    ) {
+        // The first message of a pull names the tag rather than a layer
+        if status.starts_with("Pulling from") {
+            return;
+        }
+
+        let progress = self.layers.entry(layer.to_string()).or_default();
+        match status {
+            "Downloading" => {
+                progress.downloaded = current.unwrap_or(progress.downloaded);
+                progress.total = total.unwrap_or(progress.total);
+            }
+            "Download complete" => progress.downloaded = progress.total,
+            "Pull complete" | "Already exists" => {
+                progress.downloaded = progress.total;
+                progress.done = true;
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn progress(&self, done: bool) -> PullProgress {
+        PullProgress {
+            reference: self.reference.clone(),
+            downloaded_bytes: self.layers.values().map(|layer| layer.downloaded).sum(),
+            total_bytes: self.layers.values().map(|layer| layer.total).sum(),
+            layers_done: self.layers.values().filter(|layer| layer.done).count(),
+            layers_total: self.layers.len(),
+            done,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregates_layer_progress() {
+        let mut tracker = PullTracker::new("sigp/lighthouse:v7.0.0");
+        tracker.update("v7.0.0", "Pulling from sigp/lighthouse", None, None);
+        tracker.update("a", "Already exists", None, None);
+        tracker.update("b", "Pulling fs layer", None, None);
+        tracker.update("c", "Downloading", Some(100), Some(400));
+        tracker.update("b", "Downloading", Some(50), Some(200));
+
+        let progress = tracker.progress(false);
+        assert_eq!(progress.layers_total, 3);
+        assert_eq!(progress.layers_done, 1);
+        assert_eq!(progress.downloaded_bytes, 150);
+        assert_eq!(progress.total_bytes, 600);
+
+        tracker.update("b", "Download complete", None, None);
+        tracker.update("b", "Pull complete", None, None);
+        let progress = tracker.progress(false);
+        assert_eq!(progress.layers_done, 2);
+        assert_eq!(progress.downloaded_bytes, 300);
+    }
+}
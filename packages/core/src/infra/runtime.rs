@@ -0,0 +1,107 @@
+use crate::domain::container::Container;
+use crate::domain::logs::{LogLineStream, LogsQuery};
+use crate::domain::package_status::{ContainerState, ContainerStatus};
+use crate::domain::ports::PublishedPort;
+use crate::infra::ports;
+use crate::infra::pull_progress::OnPullProgress;
+use eyre::Result;
+use std::future::Future;
+
+/// The operations Kittynode needs from a container engine.
+///
+/// Package flows are written against this trait rather than a concrete engine, so they can run
+/// against Docker in production and against an in-memory fake in tests.
+pub(crate) trait ContainerRuntime: Sync {
+    /// Returns the directory the engine stores images, containers and volumes in.
+    fn data_root(&self) -> impl Future<Output = Result<String>> + Send;
+
+    /// Returns whether a network with the given name exists.
+    fn network_exists(&self, name: &str) -> impl Future<Output = Result<bool>> + Send;
+
+    /// Creates a bridge network.
+    fn create_network(&self, name: &str) -> impl Future<Output = Result<()>> + Send;
+
+    /// Removes a network.
+    fn remove_network(&self, name: &str) -> impl Future<Output = Result<()>> + Send;
+
+    /// Pulls an image by reference (`image:tag` or `image@digest`), reporting progress as it
+    /// goes. Fails if the engine reports an error for any part of the pull.
+    fn pull_image(
+        &self,
+        reference: &str,
+        on_progress: OnPullProgress<'_>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Returns the repository digest of a pulled image, if the engine knows it.
+    fn image_digest(&self, reference: &str) -> impl Future<Output = Result<Option<String>>> + Send;
+
+    /// Removes an image from the local image store.
+    fn remove_image(&self, reference: &str) -> impl Future<Output = Result<()>> + Send;
+
+    /// Returns whether a container with the given name exists, running or not.
+    fn container_exists(&self, name: &str) -> impl Future<Output = Result<bool>> + Send;
+
+    /// Creates a container without starting it.
+    fn create_container(&self, container: &Container) -> impl Future<Output = Result<()>> + Send;
+
+    /// Starts a created or stopped container.
+    fn start_container(&self, name: &str) -> impl Future<Output = Result<()>> + Send;
+
+    /// Stops a running container, leaving it in place to be started again.
+    ///
+    /// The container is killed if it has not exited after `timeout_secs`, or after its own stop
+    /// timeout when `None`.
+    fn stop_container(
+        &self,
+        name: &str,
+        timeout_secs: Option<i64>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Returns the runtime status of a container, with a `Missing` state if it does not exist.
+    fn container_status(&self, name: &str) -> impl Future<Output = Result<ContainerStatus>> + Send;
+
+    /// Returns whether a container exists and is currently running.
+    fn is_container_running(&self, name: &str) -> impl Future<Output = Result<bool>> + Send {
+        async move { Ok(self.container_status(name).await?.state == ContainerState::Running) }
+    }
+
+    /// Stops and removes every container matching the given name, stopping them as
+    /// [`stop_container`](Self::stop_container) does. A container that fails to stop is not
+    /// removed.
+    fn remove_container(
+        &self,
+        name: &str,
+        timeout_secs: Option<i64>,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Connects a container to a network.
+    fn connect_network(
+        &self,
+        network: &str,
+        container: &str,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Disconnects a container from a network.
+    fn disconnect_network(
+        &self,
+        network: &str,
+        container: &str,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Returns the names of the networks a container is connected to.
+    fn container_networks(&self, name: &str) -> impl Future<Output = Result<Vec<String>>> + Send;
+
+    /// Returns the host ports published by running containers.
+    fn published_ports(&self) -> impl Future<Output = Result<Vec<PublishedPort>>> + Send;
+
+    /// Returns whether a host port can be bound, probing the host network by default.
+    fn is_host_port_free(&self, port: &PublishedPort) -> bool {
+        ports::is_host_port_free(port)
+    }
+
+    /// Removes a named volume.
+    fn remove_volume(&self, name: &str) -> impl Future<Output = Result<()>> + Send;
+
+    /// Streams the logs of a container selected by `query`.
+    fn container_logs(&self, name: &str, query: &LogsQuery) -> LogLineStream;
+}
@@ -0,0 +1,79 @@
+use crate::domain::tls::fingerprint;
+use crate::infra::file::{kittynode_path, write_private_file};
+use eyre::{Context, ContextCompat, Result};
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
+use std::{fs, path::Path, path::PathBuf};
+use tracing::info;
+
+pub struct TlsStore;
+
+impl TlsStore {
+    /// Returns the path of the self-signed certificate.
+    pub fn cert_path() -> Result<PathBuf> {
+        Ok(kittynode_path()?.join("tls").join("cert.pem"))
+    }
+
+    /// Returns the path of the private key of the self-signed certificate.
+    pub fn key_path() -> Result<PathBuf> {
+        Ok(kittynode_path()?.join("tls").join("key.pem"))
+    }
+}
+
+/// Writes a self-signed certificate valid for the given host names, with its key readable
+/// only by the current user.
+pub(crate) fn generate_self_signed(
+    cert_path: &Path,
+    key_path: &Path,
+    host_names: Vec<String>,
+) -> Result<()> {
+    let certified = rcgen::generate_simple_self_signed(host_names)
+        .wrap_err("Failed to generate a self-signed certificate")?;
+    write_private_file(key_path, &certified.key_pair.serialize_pem())?;
+    fs::write(cert_path, certified.cert.pem()).wrap_err("Failed to write the certificate")?;
+    info!("Generated a self-signed certificate at {:?}", cert_path);
+    Ok(())
+}
+
+/// Checks a PEM certificate and key can be served, returning the certificate fingerprint.
+pub(crate) fn check_certificate(cert_path: &Path, key_path: &Path) -> Result<String> {
+    let cert_pem =
+        fs::read(cert_path).wrap_err_with(|| format!("Failed to read {}", cert_path.display()))?;
+    let cert = CertificateDer::pem_slice_iter(&cert_pem)
+        .next()
+        .wrap_err_with(|| format!("No certificate found in {}", cert_path.display()))?
+        .wrap_err_with(|| format!("Invalid certificate in {}", cert_path.display()))?;
+    let key_pem =
+        fs::read(key_path).wrap_err_with(|| format!("Failed to read {}", key_path.display()))?;
+    PrivateKeyDer::from_pem_slice(&key_pem)
+        .wrap_err_with(|| format!("No private key found in {}", key_path.display()))?;
+    Ok(fingerprint(&cert))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn generates_a_certificate_with_a_stable_fingerprint() {
+        let dir = tempdir().unwrap();
+        let cert_path = dir.path().join("cert.pem");
+        let key_path = dir.path().join("key.pem");
+
+        generate_self_signed(&cert_path, &key_path, vec!["localhost".to_string()]).unwrap();
+        let fingerprint = check_certificate(&cert_path, &key_path).unwrap();
+        assert_eq!(fingerprint.len(), 32 * 3 - 1);
+        assert_eq!(
+            check_certificate(&cert_path, &key_path).unwrap(),
+            fingerprint
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&key_path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert!(check_certificate(&key_path, &cert_path).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use crate::infra::file::{create_private_file, kittynode_path, write_private_file};
+use chrono::Utc;
+use eyre::Result;
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+/// Stores keystore passwords and slashing protection interchanges under `.kittynode/validator`,
+/// readable only by the current user.
+pub struct ValidatorStore;
+
+impl ValidatorStore {
+    pub(crate) fn save_password(pubkey: &str, password: &str) -> Result<()> {
+        write_private_file(&Self::password_path(pubkey)?, password)
+    }
+
+    pub(crate) fn remove_password(pubkey: &str) -> Result<()> {
+        match fs::remove_file(Self::password_path(pubkey)?) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Saves an EIP-3076 slashing protection interchange, returning its path.
+    pub(crate) fn save_slashing_protection(interchange: &str) -> Result<PathBuf> {
+        save_interchange(
+            &Self::validator_dir()?.join("slashing_protection"),
+            interchange,
+        )
+    }
+
+    fn password_path(pubkey: &str) -> Result<PathBuf> {
+        Ok(Self::validator_dir()?
+            .join("passwords")
+            .join(format!("{pubkey}.txt")))
+    }
+
+    fn validator_dir() -> Result<PathBuf> {
+        Ok(kittynode_path()?.join("validator"))
+    }
+}
+
+/// Saves an interchange in a new file named after the current time, never overwriting an
+/// earlier one.
+fn save_interchange(dir: &Path, interchange: &str) -> Result<PathBuf> {
+    let timestamp = Utc::now().format("%Y%m%dT%H%M%S%.6fZ");
+    let mut attempt = 0;
+    loop {
+        let file_name = match attempt {
+            0 => format!("{timestamp}.json"),
+            n => format!("{timestamp}-{n}.json"),
+        };
+        let path = dir.join(file_name);
+        match create_private_file(&path, interchange) {
+            Err(e)
+                if e.downcast_ref::<std::io::Error>()
+                    .is_some_and(|e| e.kind() == ErrorKind::AlreadyExists) =>
+            {
+                attempt += 1;
+            }
+            result => return result.map(|()| path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn keeps_every_saved_interchange() {
+        let temp_dir = tempdir().unwrap();
+        let paths: Vec<_> = (0..3)
+            .map(|i| save_interchange(temp_dir.path(), &i.to_string()).unwrap())
+            .collect();
+        for (i, path) in paths.iter().enumerate() {
+            assert_eq!(fs::read_to_string(path).unwrap(), i.to_string());
+        }
+    }
+}
@@ -1 +1,9 @@
-pub(crate) mod ethereum;
+/// Manifests for the packages that ship with `kittynode-core`, keyed by file name.
+pub(crate) const BUILTIN_MANIFESTS: &[(&str, &str)] = &[
+    ("ethereum.toml", include_str!("ethereum.toml")),
+    ("monitoring.toml", include_str!("monitoring.toml")),
+];
+
+/// Grafana dashboards provisioned by monitoring packages, keyed by file name.
+pub(crate) const BUILTIN_DASHBOARDS: &[(&str, &str)] =
+    &[("ethereum.json", include_str!("dashboards/ethereum.json"))];
@@ -1,28 +1,60 @@
 use eyre::Result;
+use kittynode_client::{Client, Pairing};
+use kittynode_core::domain::config_schema::{ConfigFieldError, ConfigValidationError};
+use kittynode_core::domain::image::PullProgress;
+use kittynode_core::domain::logs::{LogLine, LogsQuery};
+use kittynode_core::domain::metrics::PackageMetrics;
+use kittynode_core::domain::node_status::EthereumNodeStatus;
 use kittynode_core::domain::package::{Package, PackageConfig};
+use kittynode_core::domain::package_status::PackageStatus;
+use kittynode_core::domain::ports::PortConflict;
+use kittynode_core::domain::requirements::RequirementsReport;
 use kittynode_core::domain::system_info::SystemInfo;
+use kittynode_core::domain::validator::{
+    KeystoreImport, KeystoreImportResult, RemovedValidators, ValidatorKey,
+};
+use serde::Serialize;
 use std::collections::HashMap;
-use std::sync::LazyLock;
-use tauri_plugin_http::reqwest;
+use std::sync::{LazyLock, Mutex};
+use tauri::{AppHandle, Emitter, async_runtime::JoinHandle};
+use tokio_stream::StreamExt;
 use tracing::info;
 
-pub static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+/// The client of the paired server, kept to reuse its connections.
+static CLIENT: LazyLock<Mutex<Option<Client>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Returns a client of the paired server, with the API token and certificate fingerprint
+/// recorded on pairing.
+fn client(server_url: &str) -> Result<Client, String> {
+    let mut cached = CLIENT.lock().unwrap();
+    if let Some(client) = cached.as_ref()
+        && client.server_url() == server_url.trim_end_matches('/')
+    {
+        return Ok(client.clone());
+    }
+    let client = Client::new(
+        server_url,
+        kittynode_core::application::get_server_token().map_err(|e| e.to_string())?,
+        kittynode_core::application::get_server_fingerprint().map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())?;
+    *cached = Some(client.clone());
+    Ok(client)
+}
+
+/// Running log streams, keyed by container name.
+static LOG_STREAMS: LazyLock<Mutex<HashMap<String, JoinHandle<()>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
 
 #[tauri::command]
 async fn add_capability(name: String, server_url: String) -> Result<(), String> {
     info!("Adding capability: {}", name);
 
     if !server_url.is_empty() {
-        let url = format!("{}/add_capability/{}", server_url, name);
-        let res = HTTP_CLIENT
-            .post(&url)
-            .send()
+        client(&server_url)?
+            .add_capability(&name)
             .await
-            .map_err(|e| e.to_string())?;
-        if !res.status().is_success() {
-            return Err(format!("Failed to add capability: {}", res.status()));
-        }
-        Ok(())
+            .map_err(|e| e.to_string())
     } else {
         kittynode_core::application::add_capability(&name).map_err(|e| e.to_string())
     }
@@ -33,16 +65,10 @@ async fn remove_capability(name: String, server_url: String) -> Result<(), Strin
     info!("Removing capability: {}", name);
 
     if !server_url.is_empty() {
-        let url = format!("{}/remove_capability/{}", server_url, name);
-        let res = HTTP_CLIENT
-            .post(&url)
-            .send()
+        client(&server_url)?
+            .remove_capability(&name)
             .await
-            .map_err(|e| e.to_string())?;
-        if !res.status().is_success() {
-            return Err(format!("Failed to remove capability: {}", res.status()));
-        }
-        Ok(())
+            .map_err(|e| e.to_string())
     } else {
         kittynode_core::application::remove_capability(&name).map_err(|e| e.to_string())
     }
@@ -53,29 +79,10 @@ async fn get_capabilities(server_url: String) -> Result<Vec<String>, String> {
     info!("Getting capabilities");
 
     if !server_url.is_empty() {
-        let url = format!("{}/get_capabilities", server_url);
-        let res = HTTP_CLIENT
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-
-        let status = res.status();
-        let error_text = res.text().await.unwrap_or_default();
-
-        if !status.is_success() {
-            return Err(format!(
-                "Failed to get capabilities: {} - {}",
-                status, error_text
-            ));
-        }
-
-        let res = HTTP_CLIENT
-            .get(&url)
-            .send()
+        client(&server_url)?
+            .get_capabilities()
             .await
-            .map_err(|e| e.to_string())?;
-        res.json::<Vec<String>>().await.map_err(|e| e.to_string())
+            .map_err(|e| e.to_string())
     } else {
         kittynode_core::application::get_capabilities().map_err(|e| e.to_string())
     }
@@ -99,31 +106,155 @@ async fn get_installed_packages(server_url: String) -> Result<Vec<Package>, Stri
     info!("Getting installed packages");
 
     if !server_url.is_empty() {
-        let url = format!("{}/get_installed_packages", server_url);
-        let res = HTTP_CLIENT
-            .get(&url)
-            .send()
+        client(&server_url)?
+            .get_installed_packages()
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| e.to_string())
+    } else {
+        kittynode_core::application::get_installed_packages()
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
 
-        let status = res.status();
-        let error_text = res.text().await.unwrap_or_default();
+#[tauri::command]
+async fn get_package_status(name: String, server_url: String) -> Result<PackageStatus, String> {
+    info!("Getting status of package: {}", name);
 
-        if !status.is_success() {
-            return Err(format!(
-                "Failed to get installed packages: {} - {}",
-                status, error_text
-            ));
-        }
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .get_package_status(&name)
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        kittynode_core::application::get_package_status(&name)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
 
-        let res = HTTP_CLIENT
-            .get(&url)
-            .send()
+#[tauri::command]
+async fn get_package_metrics(name: String, server_url: String) -> Result<PackageMetrics, String> {
+    info!("Getting metrics of package: {}", name);
+
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .get_package_metrics(&name)
             .await
-            .map_err(|e| e.to_string())?;
-        res.json::<Vec<Package>>().await.map_err(|e| e.to_string())
+            .map_err(|e| e.to_string())
     } else {
-        kittynode_core::application::get_installed_packages()
+        kittynode_core::application::get_package_metrics(&name)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[tauri::command]
+async fn get_ethereum_node_status(server_url: String) -> Result<EthereumNodeStatus, String> {
+    info!("Getting Ethereum node status");
+
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .get_ethereum_node_status()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        kittynode_core::application::get_ethereum_node_status()
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[tauri::command]
+async fn get_validators(server_url: String) -> Result<Vec<ValidatorKey>, String> {
+    info!("Getting validators");
+
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .get_validators()
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        kittynode_core::application::get_validators()
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[tauri::command]
+async fn import_validator_keystores(
+    keystores: Vec<KeystoreImport>,
+    slashing_protection: Option<String>,
+    server_url: String,
+) -> Result<Vec<KeystoreImportResult>, String> {
+    info!("Importing {} validator keystores", keystores.len());
+
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .import_validator_keystores(keystores, slashing_protection)
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        kittynode_core::application::import_validator_keystores(keystores, slashing_protection)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[tauri::command]
+async fn remove_validators(
+    pubkeys: Vec<String>,
+    server_url: String,
+) -> Result<RemovedValidators, String> {
+    info!("Removing {} validators", pubkeys.len());
+
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .remove_validators(pubkeys)
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        kittynode_core::application::remove_validators(pubkeys)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[tauri::command]
+async fn set_validator_fee_recipient(
+    pubkey: String,
+    address: String,
+    server_url: String,
+) -> Result<(), String> {
+    info!("Setting the fee recipient of validator {}", pubkey);
+
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .set_validator_fee_recipient(&pubkey, &address)
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        kittynode_core::application::set_validator_fee_recipient(&pubkey, &address)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[tauri::command]
+async fn set_validator_graffiti(
+    pubkey: String,
+    graffiti: String,
+    server_url: String,
+) -> Result<(), String> {
+    info!("Setting the graffiti of validator {}", pubkey);
+
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .set_validator_graffiti(&pubkey, &graffiti)
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        kittynode_core::application::set_validator_graffiti(&pubkey, &graffiti)
             .await
             .map_err(|e| e.to_string())
     }
@@ -136,19 +267,57 @@ async fn is_docker_running() -> bool {
 }
 
 #[tauri::command]
-async fn install_package(name: String, server_url: String) -> Result<(), String> {
+async fn check_requirements(
+    name: String,
+    server_url: String,
+) -> Result<RequirementsReport, String> {
+    info!("Checking requirements of package: {}", name);
+
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .check_requirements(&name)
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        kittynode_core::application::check_requirements(&name)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[tauri::command]
+async fn check_port_conflicts(
+    name: String,
+    server_url: String,
+) -> Result<Vec<PortConflict>, String> {
+    info!("Checking port conflicts of package: {}", name);
+
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .check_port_conflicts(&name)
+            .await
+            .map_err(|e| e.to_string())
+    } else {
+        kittynode_core::application::check_port_conflicts(&name)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Installs a package, sending image pull progress to the frontend as `pull-progress` events.
+#[tauri::command]
+async fn install_package(app: AppHandle, name: String, server_url: String) -> Result<(), String> {
+    let emit_progress = |progress: &PullProgress| {
+        let _ = app.emit("pull-progress", progress);
+    };
+
     if !server_url.is_empty() {
-        let url = format!("{}/install_package/{}", server_url, name);
-        let res = HTTP_CLIENT
-            .post(&url)
-            .send()
+        client(&server_url)?
+            .install_package_with_progress(&name, emit_progress)
             .await
             .map_err(|e| e.to_string())?;
-        if !res.status().is_success() {
-            return Err(format!("Failed to install package: {}", res.status()));
-        }
     } else {
-        kittynode_core::application::install_package(&name)
+        kittynode_core::application::install_package_with_progress(&name, emit_progress)
             .await
             .map_err(|e| e.to_string())?;
     }
@@ -157,6 +326,57 @@ async fn install_package(name: String, server_url: String) -> Result<(), String>
     Ok(())
 }
 
+#[tauri::command]
+async fn start_package(name: String, server_url: String) -> Result<(), String> {
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .start_package(&name)
+            .await
+            .map_err(|e| e.to_string())?;
+    } else {
+        kittynode_core::application::start_package(&name)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    info!("Successfully started package: {}", name);
+    Ok(())
+}
+
+#[tauri::command]
+async fn stop_package(name: String, server_url: String) -> Result<(), String> {
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .stop_package(&name)
+            .await
+            .map_err(|e| e.to_string())?;
+    } else {
+        kittynode_core::application::stop_package(&name)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    info!("Successfully stopped package: {}", name);
+    Ok(())
+}
+
+#[tauri::command]
+async fn restart_package(name: String, server_url: String) -> Result<(), String> {
+    if !server_url.is_empty() {
+        client(&server_url)?
+            .restart_package(&name)
+            .await
+            .map_err(|e| e.to_string())?;
+    } else {
+        kittynode_core::application::restart_package(&name)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    info!("Successfully restarted package: {}", name);
+    Ok(())
+}
+
 #[tauri::command]
 async fn delete_package(
     name: String,
@@ -164,15 +384,10 @@ async fn delete_package(
     server_url: String,
 ) -> Result<(), String> {
     if !server_url.is_empty() {
-        let url = format!("{}/delete_package/{}", server_url, name);
-        let res = HTTP_CLIENT
-            .post(&url)
-            .send()
+        client(&server_url)?
+            .delete_package(&name, include_images)
             .await
             .map_err(|e| e.to_string())?;
-        if !res.status().is_success() {
-            return Err(format!("Failed to delete package: {}", res.status()));
-        }
     } else {
         kittynode_core::application::delete_package(&name, include_images)
             .await
@@ -188,16 +403,10 @@ async fn delete_kittynode(server_url: String) -> Result<(), String> {
     info!("Deleting .kittynode directory");
 
     if !server_url.is_empty() {
-        let url = format!("{}/delete_kittynode", server_url);
-        let res = HTTP_CLIENT
-            .post(&url)
-            .send()
+        client(&server_url)?
+            .delete_kittynode()
             .await
-            .map_err(|e| e.to_string())?;
-        if !res.status().is_success() {
-            return Err(format!("Failed to delete Kittynode: {}", res.status()));
-        }
-        Ok(())
+            .map_err(|e| e.to_string())
     } else {
         kittynode_core::application::delete_kittynode().map_err(|e| e.to_string())
     }
@@ -208,38 +417,45 @@ async fn system_info(server_url: String) -> Result<SystemInfo, String> {
     info!("Getting system info");
 
     if !server_url.is_empty() {
-        let url = format!("{}/get_system_info", server_url);
-        let res = HTTP_CLIENT
-            .get(&url)
-            .send()
+        client(&server_url)?
+            .get_system_info()
             .await
-            .map_err(|e| e.to_string())?;
-
-        if !res.status().is_success() {
-            return Err(format!("Failed to get system info: {}", res.status()));
-        }
-
-        res.json::<SystemInfo>().await.map_err(|e| e.to_string())
+            .map_err(|e| e.to_string())
     } else {
         kittynode_core::application::get_system_info().map_err(|e| e.to_string())
     }
 }
 
+#[tauri::command]
+async fn pair_server(server_url: String, code: String, name: String) -> Result<String, String> {
+    info!("Pairing with server: {}", server_url);
+
+    // The server certificate is pinned on first use, as shown to the user to compare with
+    // the fingerprint the server logs
+    let pairing = Client::pair(&server_url, &code, &name)
+        .await
+        .map_err(|e| e.to_string())?;
+    pairing.save().map_err(|e| e.to_string())?;
+    *CLIENT.lock().unwrap() = Some(pairing.client);
+    Ok(pairing.fingerprint)
+}
+
+#[tauri::command]
+async fn unpair_server() -> Result<(), String> {
+    info!("Unpairing from server");
+    *CLIENT.lock().unwrap() = None;
+    Pairing::forget().map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn init_kittynode(server_url: String) -> Result<(), String> {
     info!("Initializing Kittynode");
 
     if !server_url.is_empty() {
-        let url = format!("{}/init_kittynode", server_url);
-        let res = HTTP_CLIENT
-            .post(&url)
-            .send()
+        client(&server_url)?
+            .init_kittynode()
             .await
-            .map_err(|e| e.to_string())?;
-        if !res.status().is_success() {
-            return Err(format!("Failed to initialize Kittynode: {}", res.status()));
-        }
-        Ok(())
+            .map_err(|e| e.to_string())
     } else {
         kittynode_core::application::init_kittynode().map_err(|e| e.to_string())
     }
@@ -248,49 +464,118 @@ async fn init_kittynode(server_url: String) -> Result<(), String> {
 #[tauri::command]
 async fn get_container_logs(
     container_name: String,
-    tail_lines: Option<usize>,
+    query: LogsQuery,
     server_url: String,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<LogLine>, String> {
     info!(
-        "Getting logs for container: {} (tail: {:?})",
-        container_name, tail_lines
+        "Getting logs for container: {} ({:?})",
+        container_name, query
     );
 
     if !server_url.is_empty() {
-        let url = format!("{}/logs/{}", server_url, container_name);
-        // Add tail_lines to query params if present
-        let url = if let Some(n) = tail_lines {
-            format!("{}?tail={}", url, n)
-        } else {
-            url
-        };
-
-        let res = HTTP_CLIENT
-            .get(&url)
-            .send()
+        client(&server_url)?
+            .get_container_logs(&container_name, &query)
             .await
-            .map_err(|e| e.to_string())?;
-        if !res.status().is_success() {
-            return Err(format!("Failed to get logs: {}", res.status()));
-        }
-        res.json::<Vec<String>>().await.map_err(|e| e.to_string())
+            .map_err(|e| e.to_string())
     } else {
-        kittynode_core::application::get_container_logs(&container_name, tail_lines)
+        kittynode_core::application::get_container_logs(&container_name, &query)
             .await
             .map_err(|e| e.to_string())
     }
 }
 
+#[derive(Clone, Serialize)]
+struct ContainerLogEvent {
+    container_name: String,
+    line: LogLine,
+}
+
+#[derive(Clone, Serialize)]
+struct ContainerLogErrorEvent {
+    container_name: String,
+    error: String,
+}
+
+/// Streams container logs to the frontend as `container-log` events, replacing any stream
+/// already running for the container. Failures are sent as `container-log-error` events.
+#[tauri::command]
+async fn stream_container_logs(
+    app: AppHandle,
+    container_name: String,
+    query: LogsQuery,
+    server_url: String,
+) -> Result<(), String> {
+    info!(
+        "Streaming logs for container: {} ({:?})",
+        container_name, query
+    );
+
+    let name = container_name.clone();
+    let task = tauri::async_runtime::spawn(async move {
+        let emit_line = |line: LogLine| {
+            app.emit(
+                "container-log",
+                ContainerLogEvent {
+                    container_name: name.clone(),
+                    line,
+                },
+            )
+            .map_err(|e| e.to_string())
+        };
+        let result = forward_logs(&server_url, &name, &query, emit_line).await;
+        if let Err(error) = result {
+            let _ = app.emit(
+                "container-log-error",
+                ContainerLogErrorEvent {
+                    container_name: name.clone(),
+                    error,
+                },
+            );
+        }
+    });
+
+    if let Some(previous) = LOG_STREAMS.lock().unwrap().insert(container_name, task) {
+        previous.abort();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn stop_container_logs(container_name: String) -> Result<(), String> {
+    info!("Stopping log stream for container: {}", container_name);
+    if let Some(task) = LOG_STREAMS.lock().unwrap().remove(&container_name) {
+        task.abort();
+    }
+    Ok(())
+}
+
+async fn forward_logs(
+    server_url: &str,
+    container_name: &str,
+    query: &LogsQuery,
+    emit_line: impl Fn(LogLine) -> Result<(), String>,
+) -> Result<(), String> {
+    let mut stream = if !server_url.is_empty() {
+        client(server_url)?
+            .stream_container_logs(container_name, query)
+            .await
+    } else {
+        kittynode_core::application::stream_container_logs(container_name, query)
+    }
+    .map_err(|e| e.to_string())?;
+    while let Some(line) = stream.next().await {
+        emit_line(line.map_err(|e| e.to_string())?)?;
+    }
+    Ok(())
+}
+
 #[tauri::command]
 async fn get_package_config(name: String, server_url: String) -> Result<PackageConfig, String> {
     if !server_url.is_empty() {
-        let url = format!("{}/get_package_config/{}", server_url, name);
-        let res = HTTP_CLIENT
-            .get(&url)
-            .send()
+        client(&server_url)?
+            .get_package_config(&name)
             .await
-            .map_err(|e| e.to_string())?;
-        res.json::<PackageConfig>().await.map_err(|e| e.to_string())
+            .map_err(|e| e.to_string())
     } else {
         kittynode_core::application::get_package_config(&name)
             .await
@@ -298,29 +583,39 @@ async fn get_package_config(name: String, server_url: String) -> Result<PackageC
     }
 }
 
+/// The error of a config update, carrying field-level errors when the config is invalid.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+enum UpdateConfigError {
+    Invalid { errors: Vec<ConfigFieldError> },
+    Failed { message: String },
+}
+
+impl From<String> for UpdateConfigError {
+    fn from(message: String) -> Self {
+        UpdateConfigError::Failed { message }
+    }
+}
+
 #[tauri::command]
 async fn update_package_config(
     name: String,
     config: PackageConfig,
     server_url: String,
-) -> Result<(), String> {
-    if !server_url.is_empty() {
-        let url = format!("{}/update_package_config/{}", server_url, name);
-        let res = HTTP_CLIENT
-            .post(&url)
-            .json(&config)
-            .send()
+) -> Result<(), UpdateConfigError> {
+    let result = if !server_url.is_empty() {
+        client(&server_url)?
+            .update_package_config(&name, config)
             .await
-            .map_err(|e| e.to_string())?;
-        if !res.status().is_success() {
-            return Err(format!("Failed to update package config: {}", res.status()));
-        }
-        Ok(())
     } else {
-        kittynode_core::application::update_package_config(&name, config)
-            .await
-            .map_err(|e| e.to_string())
-    }
+        kittynode_core::application::update_package_config(&name, config).await
+    };
+    result.map_err(|e| match e.downcast::<ConfigValidationError>() {
+        Ok(error) => UpdateConfigError::Invalid {
+            errors: error.errors,
+        },
+        Err(e) => e.to_string().into(),
+    })
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -338,16 +633,33 @@ pub fn run() -> Result<()> {
         .invoke_handler(tauri::generate_handler![
             get_packages,
             get_installed_packages,
+            get_package_status,
+            get_package_metrics,
+            get_ethereum_node_status,
+            get_validators,
+            import_validator_keystores,
+            remove_validators,
+            set_validator_fee_recipient,
+            set_validator_graffiti,
             is_docker_running,
+            check_requirements,
+            check_port_conflicts,
             install_package,
+            start_package,
+            stop_package,
+            restart_package,
             delete_package,
             delete_kittynode,
             system_info,
             init_kittynode,
+            pair_server,
+            unpair_server,
             add_capability,
             remove_capability,
             get_capabilities,
             get_container_logs,
+            stream_container_logs,
+            stop_container_logs,
             get_package_config,
             update_package_config
         ])
@@ -0,0 +1,30 @@
+export type ConfigType =
+  | "string"
+  | "integer"
+  | "boolean"
+  | "port"
+  | "address"
+  | "memory"
+  | "cpus";
+
+export interface ConfigField {
+  key: string;
+  type: ConfigType;
+  allowed: string[];
+  default: string;
+  description: string;
+  recreate: boolean;
+}
+
+export interface ConfigSchema {
+  fields: ConfigField[];
+}
+
+export interface ConfigFieldError {
+  key: string;
+  message: string;
+}
+
+export type UpdateConfigError =
+  | { kind: "invalid"; errors: ConfigFieldError[] }
+  | { kind: "failed"; message: string };
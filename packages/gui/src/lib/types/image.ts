@@ -0,0 +1,8 @@
+export interface PullProgress {
+  reference: string;
+  downloaded_bytes: number;
+  total_bytes: number;
+  layers_done: number;
+  layers_total: number;
+  done: boolean;
+}
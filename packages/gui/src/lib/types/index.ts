@@ -1 +1,9 @@
+export * from "./config_schema";
+export * from "./image";
+export * from "./logs";
+export * from "./metrics";
+export * from "./node_status";
 export * from "./package";
+export * from "./ports";
+export * from "./requirements";
+export * from "./validator";
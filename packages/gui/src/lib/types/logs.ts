@@ -0,0 +1,26 @@
+export type LogSource = "stdout" | "stderr";
+
+export interface LogLine {
+  source: LogSource;
+  timestamp?: string;
+  message: string;
+}
+
+export interface LogsQuery {
+  tail?: number;
+  follow?: boolean;
+  since?: string;
+  until?: string;
+  stdout?: boolean;
+  stderr?: boolean;
+}
+
+export interface ContainerLogEvent {
+  container_name: string;
+  line: LogLine;
+}
+
+export interface ContainerLogErrorEvent {
+  container_name: string;
+  error: string;
+}
@@ -0,0 +1,19 @@
+export interface JobMetrics {
+  job: string;
+  sync_height?: number;
+  peers?: number;
+  cpu_seconds?: number;
+  cpu_percent?: number;
+  disk_bytes?: number;
+  error?: string;
+}
+
+export interface MetricsSnapshot {
+  timestamp: string;
+  jobs: JobMetrics[];
+}
+
+export interface PackageMetrics {
+  package: string;
+  snapshots: MetricsSnapshot[];
+}
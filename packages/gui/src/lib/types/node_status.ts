@@ -0,0 +1,45 @@
+export interface ExecutionClientStatus {
+  reachable: boolean;
+  is_syncing: boolean;
+  current_block?: number;
+  highest_block?: number;
+  peers?: number;
+  error?: string;
+}
+
+export type ConsensusHealth =
+  | "ready"
+  | "syncing"
+  | "not_initialized"
+  | "unknown";
+
+export interface ConsensusClientStatus {
+  reachable: boolean;
+  health: ConsensusHealth;
+  head_slot?: number;
+  sync_distance?: number;
+  is_syncing: boolean;
+  is_optimistic: boolean;
+  el_offline?: boolean;
+  peers?: number;
+  error?: string;
+}
+
+export interface RelayStatus {
+  url: string;
+  reachable: boolean;
+  error?: string;
+}
+
+export interface MevBoostStatus {
+  reachable: boolean;
+  relays: RelayStatus[];
+  error?: string;
+}
+
+export interface EthereumNodeStatus {
+  execution: ExecutionClientStatus;
+  consensus: ConsensusClientStatus;
+  clients_in_agreement: boolean;
+  mev_boost?: MevBoostStatus;
+}
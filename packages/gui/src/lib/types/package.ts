@@ -1,17 +1,84 @@
+import type { ConfigSchema } from "./config_schema";
+import type { Requirements } from "./requirements";
+
 export interface Package {
   name: string;
   description: string;
   network_name: string;
   containers: Container[];
+  endpoints: Record<string, string>;
+  requirements: Requirements;
+  config_schema: ConfigSchema;
 }
 
 export interface Container {
   name: string;
   image: string;
+  tag: string;
+  digest?: string;
   cmd: string[];
   port_bindings: Record<string, { host_ip: string; host_port: string }[]>;
   volume_bindings: Binding[];
   file_bindings: Binding[];
+  restart_policy: RestartPolicy;
+  limits: ResourceLimits;
+  ulimits: Ulimit[];
+  stop_signal?: string;
+  stop_timeout_secs?: number;
+  log_config?: LogConfig;
+}
+
+export type RestartPolicy = "no" | "always" | "unless-stopped" | "on-failure";
+
+export interface ResourceLimits {
+  memory_bytes?: number;
+  nano_cpus?: number;
+}
+
+export interface Ulimit {
+  name: string;
+  soft: number;
+  hard: number;
+}
+
+export interface LogConfig {
+  driver: string;
+  options: Record<string, string>;
+}
+
+export type ContainerState =
+  | "created"
+  | "running"
+  | "paused"
+  | "restarting"
+  | "removing"
+  | "exited"
+  | "dead"
+  | "missing";
+
+export type PackageState =
+  | "not_installed"
+  | "partially_installed"
+  | "running"
+  | "stopped"
+  | "degraded"
+  | "crash_looping"
+  | "unhealthy";
+
+export interface ContainerStatus {
+  name: string;
+  state: ContainerState;
+  health?: string;
+  exit_code?: number;
+  uptime_secs?: number;
+  restart_count: number;
+  image_digest?: string;
+}
+
+export interface PackageStatus {
+  name: string;
+  state: PackageState;
+  containers: ContainerStatus[];
 }
 
 export interface Binding {
@@ -0,0 +1,12 @@
+export type PortConflictSource =
+  | { kind: "host_listener" }
+  | { kind: "container"; name: string }
+  | { kind: "package"; container: string };
+
+export interface PortConflict {
+  container: string;
+  host_ip: string;
+  host_port: number;
+  protocol: string;
+  source: PortConflictSource;
+}
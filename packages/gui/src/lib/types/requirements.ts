@@ -0,0 +1,23 @@
+export interface Requirements {
+  min_cores?: number;
+  recommended_cores?: number;
+  min_memory_gb?: number;
+  recommended_memory_gb?: number;
+  min_disk_gb?: number;
+  recommended_disk_gb?: number;
+}
+
+export type Resource = "cores" | "memory" | "disk";
+
+export type CheckStatus = "pass" | "warn" | "fail";
+
+export interface RequirementFinding {
+  resource: Resource;
+  status: CheckStatus;
+  message: string;
+}
+
+export interface RequirementsReport {
+  package: string;
+  findings: RequirementFinding[];
+}
@@ -0,0 +1,28 @@
+export interface ValidatorKey {
+  validating_pubkey: string;
+  derivation_path: string | null;
+  readonly: boolean;
+}
+
+export interface KeystoreImport {
+  keystore: string;
+  password: string;
+}
+
+export interface KeystoreImportResult {
+  pubkey: string;
+  status: "imported" | "duplicate" | "error";
+  message: string;
+}
+
+export interface KeystoreDeleteResult {
+  pubkey: string;
+  status: "deleted" | "not_active" | "not_found" | "error";
+  message: string;
+}
+
+export interface RemovedValidators {
+  results: KeystoreDeleteResult[];
+  slashing_protection: string;
+  slashing_protection_path: string;
+}
@@ -1,11 +1,21 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { Package } from "$lib/types";
+import { listen } from "@tauri-apps/api/event";
+import type {
+  EthereumNodeStatus,
+  Package,
+  PackageMetrics,
+  PackageStatus,
+  PortConflict,
+  PullProgress,
+  RequirementsReport,
+} from "$lib/types";
 import { error } from "$utils/error";
 import { serverUrlStore } from "./serverUrl.svelte";
 
 let packages = $state<{ [name: string]: Package }>({});
 let installedPackages = $state<Package[]>([]);
 let isLoading = $state(false);
+let pullProgress = $state<PullProgress | null>(null);
 
 export const packagesStore = {
   get packages() {
@@ -20,6 +30,10 @@ export const packagesStore = {
     return isLoading;
   },
 
+  get pullProgress() {
+    return pullProgress;
+  },
+
   isInstalled(packageName: string | undefined): boolean {
     if (!packageName) return false;
     return installedPackages.some((p) => p.name === packageName);
@@ -46,7 +60,44 @@ export const packagesStore = {
     }
   },
 
+  async getPackageStatus(name: string): Promise<PackageStatus> {
+    return await invoke("get_package_status", {
+      name,
+      serverUrl: serverUrlStore.serverUrl,
+    });
+  },
+
+  async getPackageMetrics(name: string): Promise<PackageMetrics> {
+    return await invoke("get_package_metrics", {
+      name,
+      serverUrl: serverUrlStore.serverUrl,
+    });
+  },
+
+  async getEthereumNodeStatus(): Promise<EthereumNodeStatus> {
+    return await invoke("get_ethereum_node_status", {
+      serverUrl: serverUrlStore.serverUrl,
+    });
+  },
+
+  async checkRequirements(name: string): Promise<RequirementsReport> {
+    return await invoke("check_requirements", {
+      name,
+      serverUrl: serverUrlStore.serverUrl,
+    });
+  },
+
+  async checkPortConflicts(name: string): Promise<PortConflict[]> {
+    return await invoke("check_port_conflicts", {
+      name,
+      serverUrl: serverUrlStore.serverUrl,
+    });
+  },
+
   async installPackage(name: string) {
+    const unlisten = await listen<PullProgress>("pull-progress", (event) => {
+      pullProgress = event.payload;
+    });
     try {
       await invoke("install_package", {
         name,
@@ -56,6 +107,45 @@ export const packagesStore = {
     } catch (e) {
       error(`Failed to install ${name}: ${e}`);
       throw e;
+    } finally {
+      unlisten();
+      pullProgress = null;
+    }
+  },
+
+  async startPackage(name: string) {
+    try {
+      await invoke("start_package", {
+        name,
+        serverUrl: serverUrlStore.serverUrl,
+      });
+    } catch (e) {
+      error(`Failed to start ${name}: ${e}`);
+      throw e;
+    }
+  },
+
+  async stopPackage(name: string) {
+    try {
+      await invoke("stop_package", {
+        name,
+        serverUrl: serverUrlStore.serverUrl,
+      });
+    } catch (e) {
+      error(`Failed to stop ${name}: ${e}`);
+      throw e;
+    }
+  },
+
+  async restartPackage(name: string) {
+    try {
+      await invoke("restart_package", {
+        name,
+        serverUrl: serverUrlStore.serverUrl,
+      });
+    } catch (e) {
+      error(`Failed to restart ${name}: ${e}`);
+      throw e;
     }
   },
 
@@ -0,0 +1,50 @@
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  KeystoreImport,
+  KeystoreImportResult,
+  RemovedValidators,
+  ValidatorKey,
+} from "$lib/types";
+import { serverUrlStore } from "./serverUrl.svelte";
+
+export const validatorsStore = {
+  async getValidators(): Promise<ValidatorKey[]> {
+    return await invoke("get_validators", {
+      serverUrl: serverUrlStore.serverUrl,
+    });
+  },
+
+  async importKeystores(
+    keystores: KeystoreImport[],
+    slashingProtection: string | null,
+  ): Promise<KeystoreImportResult[]> {
+    return await invoke("import_validator_keystores", {
+      keystores,
+      slashingProtection,
+      serverUrl: serverUrlStore.serverUrl,
+    });
+  },
+
+  async removeValidators(pubkeys: string[]): Promise<RemovedValidators> {
+    return await invoke("remove_validators", {
+      pubkeys,
+      serverUrl: serverUrlStore.serverUrl,
+    });
+  },
+
+  async setFeeRecipient(pubkey: string, address: string): Promise<void> {
+    await invoke("set_validator_fee_recipient", {
+      pubkey,
+      address,
+      serverUrl: serverUrlStore.serverUrl,
+    });
+  },
+
+  async setGraffiti(pubkey: string, graffiti: string): Promise<void> {
+    await invoke("set_validator_graffiti", {
+      pubkey,
+      graffiti,
+      serverUrl: serverUrlStore.serverUrl,
+    });
+  },
+};
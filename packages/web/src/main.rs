@@ -1,18 +1,161 @@
 use axum::{
     Router,
-    extract::{Path, Query},
-    http::StatusCode,
-    response::Json,
-    routing::{get, post},
+    extract::{Path, Query, Request},
+    http::{Method, StatusCode, header::AUTHORIZATION},
+    middleware::{self, Next},
+    response::{
+        IntoResponse, Json, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+    routing::get,
 };
-use kittynode_core::domain::logs::LogsQuery;
-use kittynode_core::domain::package::Package;
+use axum_server::tls_rustls::RustlsConfig;
+use kittynode_core::domain::auth::{
+    ADMIN_TOKEN_NAME, ApiToken, ApiTokenInfo, PairRequest, PairingCode, Scope,
+};
+use kittynode_core::domain::config_schema::ConfigValidationError;
+use kittynode_core::domain::image::{ImageLock, PullProgress};
+use kittynode_core::domain::logs::{LogLine, LogsQuery};
+use kittynode_core::domain::metrics::PackageMetrics;
+use kittynode_core::domain::node_status::EthereumNodeStatus;
+use kittynode_core::domain::package::{DeleteOptions, Package, PackageConfig};
+use kittynode_core::domain::package_status::PackageStatus;
+use kittynode_core::domain::ports::{PortConflict, PortConflictError, WEB_SERVER_PORT};
+use kittynode_core::domain::requirements::RequirementsReport;
 use kittynode_core::domain::system_info::SystemInfo;
+use kittynode_core::domain::tls::{TlsCertificate, TlsCertificatePaths};
+use kittynode_core::domain::validator::{
+    KeystoreImportRequest, KeystoreImportResult, RemovedValidators, ValidatorKey,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use tokio::sync::mpsc;
+use tokio_stream::{Stream, StreamExt, wrappers::UnboundedReceiverStream};
+use tracing::info;
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+/// Routes that can be called without an API token.
+const PUBLIC_ROUTES: [&str; 3] = ["/", "/pair", "/openapi.json"];
+
+/// Rejects requests without an API token whose scope allows them. Reading needs the read scope
+/// and anything else the admin scope.
+pub(crate) async fn require_token(
+    request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    if PUBLIC_ROUTES.contains(&request.uri().path()) {
+        return Ok(next.run(request).await);
+    }
+
+    let required = match *request.method() {
+        Method::GET | Method::HEAD => Scope::Read,
+        _ => Scope::Admin,
+    };
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or((StatusCode::UNAUTHORIZED, "Missing API token".to_string()))?;
 
+    match kittynode_core::application::authorize_token(token) {
+        Ok(Some(scope)) if scope.allows(required) => Ok(next.run(request).await),
+        Ok(Some(_)) => Err((
+            StatusCode::FORBIDDEN,
+            format!("This request needs the '{required}' scope"),
+        )),
+        Ok(None) => Err((StatusCode::UNAUTHORIZED, "Invalid API token".to_string())),
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = "system",
+    responses((status = 200, description = "A greeting, to check the server is up", body = String)),
+    security(())
+)]
 pub(crate) async fn hello_world() -> &'static str {
     "Hello World!"
 }
 
+#[utoipa::path(
+    post,
+    path = "/pair",
+    tag = "auth",
+    request_body = PairRequest,
+    responses(
+        (status = 200, description = "The new API token", body = ApiToken),
+        (status = 401, description = "Invalid or expired pairing code", body = String)
+    ),
+    security(())
+)]
+pub(crate) async fn pair_client(
+    Json(request): Json<PairRequest>,
+) -> Result<Json<ApiToken>, (StatusCode, String)> {
+    kittynode_core::application::pair_client(&request.code, &request.name)
+        .map(Json)
+        .map_err(|e| (StatusCode::UNAUTHORIZED, e.to_string()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/pairing_codes",
+    tag = "auth",
+    request_body = Scope,
+    responses((status = 200, description = "The pairing code", body = PairingCode))
+)]
+pub(crate) async fn create_pairing_code(
+    Json(scope): Json<Scope>,
+) -> Result<Json<PairingCode>, (StatusCode, String)> {
+    kittynode_core::application::create_pairing_code(scope)
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api_tokens",
+    tag = "auth",
+    responses(
+        (
+            status = 200,
+            description = "The API tokens, without their secrets",
+            body = Vec<ApiTokenInfo>
+        )
+    )
+)]
+pub(crate) async fn get_api_tokens() -> Result<Json<Vec<ApiTokenInfo>>, (StatusCode, String)> {
+    kittynode_core::application::get_api_tokens()
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api_tokens/{name}",
+    tag = "auth",
+    params(("name" = String, Path, description = "The client name of the token")),
+    responses((status = 200, description = "Done"))
+)]
+pub(crate) async fn revoke_api_token(
+    Path(name): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    kittynode_core::application::revoke_api_token(&name)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    put,
+    path = "/capabilities/{name}",
+    tag = "capabilities",
+    params(("name" = String, Path, description = "The capability name")),
+    responses((status = 200, description = "Done"))
+)]
 pub(crate) async fn add_capability(
     Path(name): Path<String>,
 ) -> Result<StatusCode, (StatusCode, String)> {
@@ -21,6 +164,13 @@ pub(crate) async fn add_capability(
     Ok(StatusCode::OK)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/capabilities/{name}",
+    tag = "capabilities",
+    params(("name" = String, Path, description = "The capability name")),
+    responses((status = 200, description = "Done"))
+)]
 pub(crate) async fn remove_capability(
     Path(name): Path<String>,
 ) -> Result<StatusCode, (StatusCode, String)> {
@@ -29,30 +179,276 @@ pub(crate) async fn remove_capability(
     Ok(StatusCode::OK)
 }
 
+#[utoipa::path(
+    get,
+    path = "/capabilities",
+    tag = "capabilities",
+    responses((status = 200, description = "The enabled capabilities", body = Vec<String>))
+)]
 pub(crate) async fn get_capabilities() -> Result<Json<Vec<String>>, (StatusCode, String)> {
     kittynode_core::application::get_capabilities()
         .map(Json)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
 }
 
-pub(crate) async fn install_package(
+#[utoipa::path(
+    get,
+    path = "/packages",
+    tag = "packages",
+    responses(
+        (
+            status = 200,
+            description = "The available packages, keyed by name",
+            body = HashMap<String, Package>
+        )
+    )
+)]
+pub(crate) async fn get_packages() -> Result<Json<HashMap<String, Package>>, (StatusCode, String)> {
+    kittynode_core::application::get_packages()
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Installs a package. A package whose host ports are taken is rejected with `409 Conflict` and
+/// its conflicts as JSON.
+#[utoipa::path(
+    post,
+    path = "/packages/{name}/install",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses(
+        (status = 200, description = "Done"),
+        (
+            status = 409,
+            description = "A port the package publishes is in use",
+            body = PortConflictError
+        )
+    )
+)]
+pub(crate) async fn install_package(Path(name): Path<String>) -> Result<StatusCode, Response> {
+    kittynode_core::application::install_package(&name)
+        .await
+        .map_err(|e| match e.downcast::<PortConflictError>() {
+            Ok(error) => (StatusCode::CONFLICT, Json(error)).into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        })?;
+    Ok(StatusCode::OK)
+}
+
+/// Installs a package, streaming image pull progress as server-sent `progress` events. The stream
+/// ends with a `done` event, or an `error` event if the install failed.
+#[utoipa::path(
+    post,
+    path = "/packages/{name}/install/stream",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses(
+        (
+            status = 200,
+            description = "Server-sent `progress` events, then `done` or `error`",
+            content_type = "text/event-stream",
+            body = PullProgress
+        )
+    )
+)]
+pub(crate) async fn install_package_stream(
+    Path(name): Path<String>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let progress_sender = sender.clone();
+    tokio::spawn(async move {
+        let on_progress = |progress: &PullProgress| {
+            let _ = progress_sender.send(Event::default().event("progress").json_data(progress));
+        };
+        let event =
+            match kittynode_core::application::install_package_with_progress(&name, on_progress)
+                .await
+            {
+                Ok(()) => Event::default().event("done").data(name),
+                Err(e) => Event::default().event("error").data(e.to_string()),
+            };
+        let _ = sender.send(Ok(event));
+    });
+    Sse::new(UnboundedReceiverStream::new(receiver)).keep_alive(KeepAlive::default())
+}
+
+#[utoipa::path(
+    get,
+    path = "/packages/{name}/requirements",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses(
+        (
+            status = 200,
+            description = "How the host meets the package requirements",
+            body = RequirementsReport
+        )
+    )
+)]
+pub(crate) async fn check_requirements(
+    Path(name): Path<String>,
+) -> Result<Json<RequirementsReport>, (StatusCode, String)> {
+    kittynode_core::application::check_requirements(&name)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/packages/{name}/port_conflicts",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses(
+        (
+            status = 200,
+            description = "The ports of the package already in use",
+            body = Vec<PortConflict>
+        )
+    )
+)]
+pub(crate) async fn check_port_conflicts(
+    Path(name): Path<String>,
+) -> Result<Json<Vec<PortConflict>>, (StatusCode, String)> {
+    kittynode_core::application::check_port_conflicts(&name)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/packages/{name}/config",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses((status = 200, description = "The package config", body = PackageConfig))
+)]
+pub(crate) async fn get_package_config(
+    Path(name): Path<String>,
+) -> Result<Json<PackageConfig>, (StatusCode, String)> {
+    kittynode_core::application::get_package_config(&name)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Updates the config of a package. A config that does not match the package schema is rejected
+/// with `422 Unprocessable Entity` and its field errors as JSON.
+#[utoipa::path(
+    put,
+    path = "/packages/{name}/config",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    request_body = PackageConfig,
+    responses(
+        (status = 200, description = "Done"),
+        (
+            status = 422,
+            description = "The config does not match the package schema",
+            body = ConfigValidationError
+        )
+    )
+)]
+pub(crate) async fn update_package_config(
+    Path(name): Path<String>,
+    Json(config): Json<PackageConfig>,
+) -> Result<StatusCode, Response> {
+    kittynode_core::application::update_package_config(&name, config)
+        .await
+        .map_err(|e| match e.downcast::<ConfigValidationError>() {
+            Ok(error) => (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        })?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    post,
+    path = "/packages/{name}/upgrade",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses((status = 200, description = "Done"))
+)]
+pub(crate) async fn upgrade_package(
     Path(name): Path<String>,
 ) -> Result<StatusCode, (StatusCode, String)> {
-    kittynode_core::application::install_package(&name)
+    kittynode_core::application::upgrade_package(&name)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     Ok(StatusCode::OK)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/packages/{name}",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name"), DeleteOptions),
+    responses((status = 200, description = "Done"))
+)]
 pub(crate) async fn delete_package(
     Path(name): Path<String>,
+    Query(options): Query<DeleteOptions>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    kittynode_core::application::delete_package(&name, options.include_images)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    post,
+    path = "/packages/{name}/start",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses((status = 200, description = "Done"))
+)]
+pub(crate) async fn start_package(
+    Path(name): Path<String>,
 ) -> Result<StatusCode, (StatusCode, String)> {
-    kittynode_core::application::delete_package(&name, false)
+    kittynode_core::application::start_package(&name)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     Ok(StatusCode::OK)
 }
 
+#[utoipa::path(
+    post,
+    path = "/packages/{name}/stop",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses((status = 200, description = "Done"))
+)]
+pub(crate) async fn stop_package(
+    Path(name): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    kittynode_core::application::stop_package(&name)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    post,
+    path = "/packages/{name}/restart",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses((status = 200, description = "Done"))
+)]
+pub(crate) async fn restart_package(
+    Path(name): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    kittynode_core::application::restart_package(&name)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    get,
+    path = "/packages/installed",
+    tag = "packages",
+    responses((status = 200, description = "The installed packages", body = Vec<Package>))
+)]
 pub(crate) async fn get_installed_packages() -> Result<Json<Vec<Package>>, (StatusCode, String)> {
     kittynode_core::application::get_installed_packages()
         .await
@@ -60,6 +456,174 @@ pub(crate) async fn get_installed_packages() -> Result<Json<Vec<Package>>, (Stat
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/packages/{name}/status",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses((status = 200, description = "The package status", body = PackageStatus))
+)]
+pub(crate) async fn get_package_status(
+    Path(name): Path<String>,
+) -> Result<Json<PackageStatus>, (StatusCode, String)> {
+    kittynode_core::application::get_package_status(&name)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/packages/{name}/metrics",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses(
+        (status = 200, description = "The recent metrics of the package", body = PackageMetrics)
+    )
+)]
+pub(crate) async fn get_package_metrics(
+    Path(name): Path<String>,
+) -> Result<Json<PackageMetrics>, (StatusCode, String)> {
+    kittynode_core::application::get_package_metrics(&name)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/packages/{name}/images",
+    tag = "packages",
+    params(("name" = String, Path, description = "The package name")),
+    responses(
+        (status = 200, description = "The images installed for each container", body = ImageLock)
+    )
+)]
+pub(crate) async fn get_installed_images(
+    Path(name): Path<String>,
+) -> Result<Json<ImageLock>, (StatusCode, String)> {
+    kittynode_core::application::get_installed_images(&name)
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ethereum/status",
+    tag = "ethereum",
+    responses(
+        (status = 200, description = "The status of the Ethereum node", body = EthereumNodeStatus)
+    )
+)]
+pub(crate) async fn get_ethereum_node_status()
+-> Result<Json<EthereumNodeStatus>, (StatusCode, String)> {
+    kittynode_core::application::get_ethereum_node_status()
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/validators",
+    tag = "ethereum",
+    responses((status = 200, description = "The validator keys", body = Vec<ValidatorKey>))
+)]
+pub(crate) async fn get_validators() -> Result<Json<Vec<ValidatorKey>>, (StatusCode, String)> {
+    kittynode_core::application::get_validators()
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/validators",
+    tag = "ethereum",
+    request_body = KeystoreImportRequest,
+    responses(
+        (status = 200, description = "The result of each import", body = Vec<KeystoreImportResult>)
+    )
+)]
+pub(crate) async fn import_validator_keystores(
+    Json(request): Json<KeystoreImportRequest>,
+) -> Result<Json<Vec<KeystoreImportResult>>, (StatusCode, String)> {
+    kittynode_core::application::import_validator_keystores(
+        request.keystores,
+        request.slashing_protection,
+    )
+    .await
+    .map(Json)
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/validators",
+    tag = "ethereum",
+    request_body = Vec<String>,
+    responses(
+        (
+            status = 200,
+            description = "The removed keys and their slashing protection",
+            body = RemovedValidators
+        )
+    )
+)]
+pub(crate) async fn remove_validators(
+    Json(pubkeys): Json<Vec<String>>,
+) -> Result<Json<RemovedValidators>, (StatusCode, String)> {
+    kittynode_core::application::remove_validators(pubkeys)
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    put,
+    path = "/validators/{pubkey}/fee_recipient",
+    tag = "ethereum",
+    params(("pubkey" = String, Path, description = "The validator public key")),
+    request_body = String,
+    responses((status = 200, description = "Done"))
+)]
+pub(crate) async fn set_validator_fee_recipient(
+    Path(pubkey): Path<String>,
+    Json(address): Json<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    kittynode_core::application::set_validator_fee_recipient(&pubkey, &address)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    put,
+    path = "/validators/{pubkey}/graffiti",
+    tag = "ethereum",
+    params(("pubkey" = String, Path, description = "The validator public key")),
+    request_body = String,
+    responses((status = 200, description = "Done"))
+)]
+pub(crate) async fn set_validator_graffiti(
+    Path(pubkey): Path<String>,
+    Json(graffiti): Json<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    kittynode_core::application::set_validator_graffiti(&pubkey, &graffiti)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(StatusCode::OK)
+}
+
+#[utoipa::path(
+    get,
+    path = "/docker/running",
+    tag = "system",
+    responses(
+        (status = 200, description = "Docker is running"),
+        (status = 503, description = "Docker is not running", body = String)
+    )
+)]
 pub(crate) async fn is_docker_running() -> Result<StatusCode, (StatusCode, String)> {
     match kittynode_core::application::is_docker_running().await {
         true => Ok(StatusCode::OK),
@@ -70,52 +634,457 @@ pub(crate) async fn is_docker_running() -> Result<StatusCode, (StatusCode, Strin
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/kittynode",
+    tag = "system",
+    responses((status = 200, description = "Done"))
+)]
 pub(crate) async fn init_kittynode() -> Result<StatusCode, (StatusCode, String)> {
     kittynode_core::application::init_kittynode()
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     Ok(StatusCode::OK)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/kittynode",
+    tag = "system",
+    responses((status = 200, description = "Done"))
+)]
 pub(crate) async fn delete_kittynode() -> Result<StatusCode, (StatusCode, String)> {
     kittynode_core::application::delete_kittynode()
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     Ok(StatusCode::OK)
 }
 
+#[utoipa::path(
+    get,
+    path = "/system_info",
+    tag = "system",
+    responses((status = 200, description = "The host hardware", body = SystemInfo))
+)]
 pub(crate) async fn get_system_info() -> Result<Json<SystemInfo>, (StatusCode, String)> {
     kittynode_core::application::get_system_info()
         .map(Json)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tls_certificate",
+    tag = "tls",
+    responses((status = 200, description = "The certificate served", body = TlsCertificate))
+)]
+pub(crate) async fn get_tls_certificate() -> Result<Json<TlsCertificate>, (StatusCode, String)> {
+    kittynode_core::application::get_tls_certificate()
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Serves a user-supplied certificate after the server restarts. An unusable certificate is
+/// rejected with `422 Unprocessable Entity`.
+#[utoipa::path(
+    put,
+    path = "/tls_certificate",
+    tag = "tls",
+    request_body = TlsCertificatePaths,
+    responses(
+        (status = 200, description = "The certificate to serve", body = TlsCertificate),
+        (status = 422, description = "The certificate or key cannot be used", body = String)
+    )
+)]
+pub(crate) async fn set_tls_certificate(
+    Json(paths): Json<TlsCertificatePaths>,
+) -> Result<Json<TlsCertificate>, (StatusCode, String)> {
+    let cert_path = std::path::Path::new(&paths.cert_path);
+    let key_path = std::path::Path::new(&paths.key_path);
+    kittynode_core::application::set_tls_certificate(Some((cert_path, key_path)))
+        .map(Json)
+        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))
+}
+
+/// Serves the self-signed certificate again after the server restarts.
+#[utoipa::path(
+    delete,
+    path = "/tls_certificate",
+    tag = "tls",
+    responses((status = 200, description = "The certificate to serve", body = TlsCertificate))
+)]
+pub(crate) async fn use_self_signed_certificate()
+-> Result<Json<TlsCertificate>, (StatusCode, String)> {
+    kittynode_core::application::set_tls_certificate(None)
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/containers/{name}/logs",
+    tag = "containers",
+    params(("name" = String, Path, description = "The container name"), LogsQuery),
+    responses((status = 200, description = "The log lines", body = Vec<LogLine>))
+)]
 pub(crate) async fn get_container_logs(
     Path(container_name): Path<String>,
     Query(params): Query<LogsQuery>,
-) -> Result<Json<Vec<String>>, (StatusCode, String)> {
-    kittynode_core::application::get_container_logs(&container_name, params.tail)
+) -> Result<Json<Vec<LogLine>>, (StatusCode, String)> {
+    kittynode_core::application::get_container_logs(&container_name, &params)
         .await
         .map(Json)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
 }
 
+/// Streams container logs as server-sent `log` events, ending with an `error` event on failure.
+#[utoipa::path(
+    get,
+    path = "/containers/{name}/logs/stream",
+    tag = "containers",
+    params(("name" = String, Path, description = "The container name"), LogsQuery),
+    responses(
+        (
+            status = 200,
+            description = "Server-sent `log` events, ending with an `error` event on failure",
+            content_type = "text/event-stream",
+            body = LogLine
+        )
+    )
+)]
+pub(crate) async fn stream_container_logs(
+    Path(container_name): Path<String>,
+    Query(params): Query<LogsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, String)> {
+    let stream = kittynode_core::application::stream_container_logs(&container_name, &params)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(|line| match line {
+            Ok(line) => Event::default().event("log").json_data(line),
+            Err(e) => Ok(Event::default().event("error").data(e.to_string())),
+        });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// The OpenAPI document of the web API, completed with the paths of [`api`].
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Kittynode", description = "The Kittynode web API."),
+    modifiers(&BearerToken),
+    security(("api_token" = []))
+)]
+struct ApiDoc;
+
+/// Documents the API tokens every route except [`PUBLIC_ROUTES`] needs.
+struct BearerToken;
+
+impl Modify for BearerToken {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_default()
+            .add_security_scheme(
+                "api_token",
+                SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+            );
+    }
+}
+
+/// Returns the routes of the web API and their OpenAPI document. The server puts each route
+/// behind [`require_token`].
+pub(crate) fn api() -> (Router, utoipa::openapi::OpenApi) {
+    OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .routes(routes!(hello_world))
+        .routes(routes!(pair_client))
+        .routes(routes!(create_pairing_code))
+        .routes(routes!(get_api_tokens))
+        .routes(routes!(revoke_api_token))
+        .routes(routes!(get_capabilities))
+        .routes(routes!(add_capability, remove_capability))
+        .routes(routes!(get_packages))
+        .routes(routes!(get_installed_packages))
+        .routes(routes!(delete_package))
+        .routes(routes!(install_package))
+        .routes(routes!(install_package_stream))
+        .routes(routes!(upgrade_package))
+        .routes(routes!(start_package))
+        .routes(routes!(stop_package))
+        .routes(routes!(restart_package))
+        .routes(routes!(get_package_config, update_package_config))
+        .routes(routes!(get_package_status))
+        .routes(routes!(get_package_metrics))
+        .routes(routes!(get_installed_images))
+        .routes(routes!(check_requirements))
+        .routes(routes!(check_port_conflicts))
+        .routes(routes!(get_ethereum_node_status))
+        .routes(routes!(
+            get_validators,
+            import_validator_keystores,
+            remove_validators
+        ))
+        .routes(routes!(set_validator_fee_recipient))
+        .routes(routes!(set_validator_graffiti))
+        .routes(routes!(get_container_logs))
+        .routes(routes!(stream_container_logs))
+        .routes(routes!(is_docker_running))
+        .routes(routes!(get_system_info))
+        .routes(routes!(init_kittynode, delete_kittynode))
+        .routes(routes!(
+            get_tls_certificate,
+            set_tls_certificate,
+            use_self_signed_certificate
+        ))
+        .split_for_parts()
+}
+
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt::init();
 
-    let app = Router::new()
-        .route("/", get(hello_world))
-        .route("/add_capability/:name", post(add_capability))
-        .route("/remove_capability/:name", post(remove_capability))
-        .route("/get_capabilities", get(get_capabilities))
-        .route("/install_package/:name", post(install_package))
-        .route("/delete_package/:name", post(delete_package))
-        .route("/get_installed_packages", get(get_installed_packages))
-        .route("/is_docker_running", get(is_docker_running))
-        .route("/init_kittynode", post(init_kittynode))
-        .route("/delete_kittynode", post(delete_kittynode))
-        .route("/get_system_info", get(get_system_info))
-        .route("/logs/:container_name", get(get_container_logs));
-
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    rustls::crypto::ring::default_provider()
+        .install_default()
+        .expect("Failed to install the TLS crypto provider");
+    kittynode_core::application::init_api_tokens().expect("Failed to create the API token");
+    kittynode_core::application::init_tls_certificate()
+        .expect("Failed to create the TLS certificate");
+    let certificate = kittynode_core::application::get_tls_certificate()
+        .expect("Failed to load the TLS certificate");
+    let tls_config = RustlsConfig::from_pem_file(&certificate.cert_path, &certificate.key_path)
+        .await
+        .expect("Failed to load the TLS certificate");
+    // Anyone holding the code gets admin access, so it is only minted until a client pairs and
+    // is printed to the terminal rather than the log
+    let tokens = kittynode_core::application::get_api_tokens().expect("Failed to load API tokens");
+    if tokens.iter().all(|token| token.name == ADMIN_TOKEN_NAME) {
+        let pairing_code = kittynode_core::application::create_pairing_code(Scope::Admin)
+            .expect("Failed to create a pairing code");
+        println!(
+            "Pair the Kittynode app with code {} before {}",
+            pairing_code.code, pairing_code.expires_at
+        );
+    }
+    info!(
+        "Serving HTTPS with certificate fingerprint {}",
+        certificate.fingerprint
+    );
+
+    let (routes, openapi) = api();
+    let app = routes
+        .route("/openapi.json", get(move || async move { Json(openapi) }))
+        .layer(middleware::from_fn(require_token));
+    axum_server::bind_rustls(
+        SocketAddr::from(([0, 0, 0, 0], WEB_SERVER_PORT)),
+        tls_config,
+    )
+    .serve(app.into_make_service())
+    .await
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::header::ALLOW;
+    use std::collections::HashSet;
+    use tower::ServiceExt;
+
+    /// The route serving each application use case, with example path parameters.
+    const ROUTES: &[(&str, Method, &str)] = &[
+        ("pair_client", Method::POST, "/pair"),
+        ("create_pairing_code", Method::POST, "/pairing_codes"),
+        ("get_api_tokens", Method::GET, "/api_tokens"),
+        ("revoke_api_token", Method::DELETE, "/api_tokens/dash"),
+        ("get_capabilities", Method::GET, "/capabilities"),
+        ("add_capability", Method::PUT, "/capabilities/ethereum"),
+        (
+            "remove_capability",
+            Method::DELETE,
+            "/capabilities/ethereum",
+        ),
+        ("get_packages", Method::GET, "/packages"),
+        ("get_installed_packages", Method::GET, "/packages/installed"),
+        ("delete_package", Method::DELETE, "/packages/ethereum"),
+        (
+            "install_package",
+            Method::POST,
+            "/packages/ethereum/install",
+        ),
+        (
+            "install_package_with_progress",
+            Method::POST,
+            "/packages/ethereum/install/stream",
+        ),
+        (
+            "upgrade_package",
+            Method::POST,
+            "/packages/ethereum/upgrade",
+        ),
+        ("start_package", Method::POST, "/packages/ethereum/start"),
+        ("stop_package", Method::POST, "/packages/ethereum/stop"),
+        (
+            "restart_package",
+            Method::POST,
+            "/packages/ethereum/restart",
+        ),
+        (
+            "get_package_config",
+            Method::GET,
+            "/packages/ethereum/config",
+        ),
+        (
+            "update_package_config",
+            Method::PUT,
+            "/packages/ethereum/config",
+        ),
+        (
+            "get_package_status",
+            Method::GET,
+            "/packages/ethereum/status",
+        ),
+        (
+            "get_package_metrics",
+            Method::GET,
+            "/packages/ethereum/metrics",
+        ),
+        (
+            "get_installed_images",
+            Method::GET,
+            "/packages/ethereum/images",
+        ),
+        (
+            "check_requirements",
+            Method::GET,
+            "/packages/ethereum/requirements",
+        ),
+        (
+            "check_port_conflicts",
+            Method::GET,
+            "/packages/ethereum/port_conflicts",
+        ),
+        ("get_ethereum_node_status", Method::GET, "/ethereum/status"),
+        ("get_validators", Method::GET, "/validators"),
+        ("import_validator_keystores", Method::POST, "/validators"),
+        ("remove_validators", Method::DELETE, "/validators"),
+        (
+            "set_validator_fee_recipient",
+            Method::PUT,
+            "/validators/0xab/fee_recipient",
+        ),
+        (
+            "set_validator_graffiti",
+            Method::PUT,
+            "/validators/0xab/graffiti",
+        ),
+        (
+            "get_container_logs",
+            Method::GET,
+            "/containers/reth-node/logs",
+        ),
+        (
+            "stream_container_logs",
+            Method::GET,
+            "/containers/reth-node/logs/stream",
+        ),
+        ("is_docker_running", Method::GET, "/docker/running"),
+        ("get_system_info", Method::GET, "/system_info"),
+        ("init_kittynode", Method::POST, "/kittynode"),
+        ("delete_kittynode", Method::DELETE, "/kittynode"),
+        ("get_tls_certificate", Method::GET, "/tls_certificate"),
+        ("set_tls_certificate", Method::PUT, "/tls_certificate"),
+        ("set_tls_certificate", Method::DELETE, "/tls_certificate"),
+    ];
+
+    /// Use cases deliberately not served, and why.
+    const NOT_ROUTED: &[(&str, &str)] = &[
+        (
+            "authorize_token",
+            "checked by require_token on every request",
+        ),
+        ("init_api_tokens", "run when the server starts"),
+        ("init_tls_certificate", "run when the server starts"),
+    ];
+
+    /// Use cases that manage the server a client is paired with, which mean nothing on the
+    /// server itself.
+    const CLIENT_ONLY: &[&str] = &[
+        "get_server_url",
+        "set_server_url",
+        "get_server_token",
+        "set_server_token",
+        "get_server_fingerprint",
+        "set_server_fingerprint",
+    ];
+
+    /// Returns the use cases exported by `kittynode_core::application`.
+    fn use_cases() -> HashSet<&'static str> {
+        include_str!("../../core/src/application/mod.rs")
+            .lines()
+            .filter_map(|line| line.strip_prefix("pub use "))
+            .filter_map(|line| line.split_once("::"))
+            .flat_map(|(_, names)| {
+                names
+                    .trim_end_matches(';')
+                    .trim_matches(|c| c == '{' || c == '}')
+                    .split(',')
+                    .map(str::trim)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_use_case_has_a_route() {
+        let routed: HashSet<&str> = ROUTES.iter().map(|(use_case, _, _)| *use_case).collect();
+        let not_routed: HashSet<&str> = NOT_ROUTED
+            .iter()
+            .map(|(use_case, _)| *use_case)
+            .chain(CLIENT_ONLY.iter().copied())
+            .collect();
+        let use_cases = use_cases();
+
+        let missing: Vec<_> = use_cases
+            .iter()
+            .filter(|use_case| !routed.contains(*use_case) && !not_routed.contains(*use_case))
+            .collect();
+        assert!(missing.is_empty(), "Use cases without a route: {missing:?}");
+        let unknown: Vec<_> = routed
+            .union(&not_routed)
+            .filter(|use_case| !use_cases.contains(*use_case))
+            .collect();
+        assert!(unknown.is_empty(), "Unknown use cases: {unknown:?}");
+    }
+
+    #[tokio::test]
+    async fn every_route_is_served() {
+        for (use_case, method, path) in ROUTES {
+            // A method no route has is answered with the methods the path has, without
+            // calling any handler
+            let request = Request::builder()
+                .method("PROBE")
+                .uri(*path)
+                .body(Body::empty())
+                .unwrap();
+            let response = api().0.oneshot(request).await.unwrap();
+            let allowed = response
+                .headers()
+                .get(ALLOW)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+            assert!(
+                allowed.split(',').any(|allowed| allowed.trim() == method),
+                "{use_case} is not served at {method} {path}"
+            );
+        }
+    }
+
+    #[test]
+    fn openapi_document_is_up_to_date() {
+        let document = api().1.to_pretty_json().unwrap() + "\n";
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+        if std::env::var_os("UPDATE_OPENAPI").is_some() {
+            std::fs::write(path, &document).unwrap();
+            return;
+        }
+        assert!(
+            document == std::fs::read_to_string(path).unwrap_or_default(),
+            "openapi.json is out of date, run `UPDATE_OPENAPI=1 cargo test -p kittynode-web`"
+        );
+    }
 }